@@ -0,0 +1,39 @@
+#ifndef SIF_ITREE_H
+#define SIF_ITREE_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Opaque handle to a tree mapping half-open uint64_t intervals to uint64_t values */
+typedef struct SifITree SifITree;
+
+/* Callback invoked for each item overlapping a query, returning false to stop the query */
+typedef bool (*SifITreeCallback)(uint64_t start, uint64_t end, uint64_t value, void *context);
+
+/* Construct a new tree from len interval starts, ends and values, to be released using sif_itree_free */
+SifITree *sif_itree_new(const uint64_t *starts, const uint64_t *ends, const uint64_t *values, size_t len);
+
+/* Memory map a file in the binary format as a tree, returning NULL if path is NULL or on failure (requires the mmap feature) */
+SifITree *sif_itree_from_mmap(const char *path);
+
+/* Release a tree, doing nothing if tree is NULL */
+void sif_itree_free(SifITree *tree);
+
+/* The number of items in the tree, which must not be NULL */
+size_t sif_itree_len(const SifITree *tree);
+
+/* Query for all intervals overlapping start..end, returning whether the query ran to completion
+ *
+ * The tree must not be NULL. If callback is NULL, false is returned without querying the tree. */
+bool sif_itree_query(const SifITree *tree, uint64_t start, uint64_t end, SifITreeCallback callback, void *context);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* SIF_ITREE_H */
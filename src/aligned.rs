@@ -0,0 +1,186 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::array::from_fn;
+use core::ops::{ControlFlow, Range};
+
+use crate::{Entry, ITree, Item, Node};
+
+/// Interval tree storing its items in buckets of `B` items aligned to 64-byte cache lines
+///
+/// A small tree over the buckets determines which buckets need to be scanned linearly, so that a bucket scan never straddles more cache lines than necessary.
+#[derive(Debug, Clone)]
+pub struct AlignedITree<K, V, const B: usize> {
+    buckets: ITree<K, usize, Box<[Node<K, usize>]>>,
+    blocks: Box<[Block<K, V, B>]>,
+    len: usize,
+}
+
+#[derive(Debug, Clone)]
+#[repr(C, align(64))]
+struct Block<K, V, const B: usize> {
+    slots: [Slot<K, V>; B],
+}
+
+#[derive(Debug, Clone)]
+#[repr(C)]
+struct Slot<K, V> {
+    start: K,
+    end: K,
+    value: V,
+}
+
+impl<K, V, const B: usize> AlignedITree<K, V, B>
+where
+    K: Ord + Clone,
+    V: Clone,
+{
+    /// Construct a new tree by sorting the given `items`
+    ///
+    /// The last bucket is padded using copies of its last item which are never yielded.
+    ///
+    /// # Panics
+    ///
+    /// If `B` is zero.
+    pub fn new<I>(items: I) -> Self
+    where
+        I: IntoIterator<Item = Item<K, V>>,
+    {
+        assert_ne!(B, 0, "buckets must not be empty");
+
+        let nodes = ITree::<K, V, Vec<Node<K, V>>>::new(items).nodes;
+
+        let mut buckets = Vec::with_capacity(nodes.len().div_ceil(B));
+        let mut blocks = Vec::with_capacity(nodes.len().div_ceil(B));
+
+        for (index, chunk) in nodes.chunks(B).enumerate() {
+            let max = chunk.iter().map(|node| &node.end).max().unwrap().clone();
+
+            buckets.push((chunk[0].start.clone()..max, index));
+
+            let last = chunk.last().unwrap();
+
+            blocks.push(Block {
+                slots: from_fn(|index| {
+                    let node = chunk.get(index).unwrap_or(last);
+
+                    Slot {
+                        start: node.start.clone(),
+                        end: node.end.clone(),
+                        value: node.value.clone(),
+                    }
+                }),
+            });
+        }
+
+        Self {
+            buckets: ITree::from_sorted(buckets),
+            blocks: blocks.into(),
+            len: nodes.len(),
+        }
+    }
+}
+
+impl<K, V, const B: usize> AlignedITree<K, V, B> {
+    /// The number of items in the tree
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the tree contains no items
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Iterate over all items in the order of their interval starts
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = Entry<'_, K, V>> + ExactSizeIterator {
+        (0..self.len).map(|index| {
+            let slot = &self.blocks[index / B].slots[index % B];
+
+            Entry::new(&slot.start, &slot.end, &slot.value)
+        })
+    }
+
+    /// Query for all intervals overlapping the given interval
+    pub fn query<'a, H>(&'a self, interval: Range<K>, mut handler: H) -> ControlFlow<()>
+    where
+        K: Ord + Clone,
+        H: FnMut(Entry<'a, K, V>) -> ControlFlow<()>,
+    {
+        self.buckets.query(interval.clone(), |entry| {
+            let index = *entry.value();
+
+            let len = (self.len - index * B).min(B);
+
+            for slot in &self.blocks[index].slots[..len] {
+                if interval.end <= slot.start {
+                    break;
+                }
+
+                if interval.start < slot.end {
+                    handler(Entry::new(&slot.start, &slot.end, &slot.value))?;
+                }
+            }
+
+            ControlFlow::Continue(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::mem::{align_of, size_of};
+
+    use proptest::{collection::vec, test_runner::TestRunner};
+
+    #[test]
+    fn blocks_are_aligned() {
+        assert_eq!(align_of::<Block<u32, u32, 4>>(), 64);
+        assert_eq!(size_of::<Block<u32, u32, 4>>(), 64);
+        assert_eq!(size_of::<Block<u32, u32, 6>>(), 128);
+    }
+
+    #[test]
+    fn query_random() {
+        const DOM: Range<i32> = -1000..1000;
+        const LEN: usize = 1000_usize;
+
+        TestRunner::default()
+            .run(
+                &(vec(DOM, LEN), vec(DOM, LEN), DOM, DOM),
+                |(start, end, query_start, query_end)| {
+                    let items = start
+                        .iter()
+                        .zip(&end)
+                        .enumerate()
+                        .map(|(index, (&start, &end))| (start..end, index))
+                        .collect::<Vec<_>>();
+
+                    let tree1 = ITree::<_, _>::new(items.iter().cloned());
+
+                    let mut result1 = Vec::new();
+                    let _ = tree1.query(query_start..query_end, |entry| {
+                        result1.push(*entry.value());
+                        ControlFlow::Continue(())
+                    });
+
+                    let tree2 = AlignedITree::<_, _, 5>::new(items);
+                    assert_eq!(tree2.len(), LEN);
+
+                    let mut result2 = Vec::new();
+                    let _ = tree2.query(query_start..query_end, |entry| {
+                        result2.push(*entry.value());
+                        ControlFlow::Continue(())
+                    });
+
+                    result1.sort_unstable();
+                    result2.sort_unstable();
+                    assert_eq!(result1, result2);
+
+                    Ok(())
+                },
+            )
+            .unwrap()
+    }
+}
@@ -0,0 +1,54 @@
+use core::marker::PhantomData;
+
+use allocator_api2::{alloc::Allocator, vec::Vec};
+
+use crate::sort::{node, sort, update_max};
+use crate::{ITree, Item, Node};
+
+/// Interval tree storing its nodes in a vector allocated using `A`
+///
+/// Requires the `allocator-api2` feature which works on stable Rust and forwards to the standard library's allocator API if its `nightly` feature is enabled.
+pub type ITreeIn<K, V, A> = ITree<K, V, Vec<Node<K, V>, A>>;
+
+impl<K, V, A> ITree<K, V, Vec<Node<K, V>, A>>
+where
+    K: Ord + Clone,
+    A: Allocator,
+{
+    /// Construct a new tree by sorting the given `items`, allocating the nodes using `alloc`
+    pub fn new_in<I>(items: I, alloc: A) -> Self
+    where
+        I: IntoIterator<Item = Item<K, V>>,
+    {
+        let items = items.into_iter();
+
+        let mut nodes = Vec::with_capacity_in(items.size_hint().0, alloc);
+        nodes.extend(items.map(node));
+
+        sort(&mut nodes);
+
+        if !nodes.is_empty() {
+            update_max(&mut nodes);
+        }
+
+        Self {
+            nodes,
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use allocator_api2::alloc::Global;
+
+    #[test]
+    fn new_in_uses_allocator() {
+        let tree = ITreeIn::new_in([(1..2, 1), (0..3, 0)], Global);
+        assert!(tree.is_valid());
+        assert!(tree.iter().eq(&[(0..3, 0), (1..2, 1)]));
+        assert_eq!(tree.as_inner().len(), 2);
+    }
+}
@@ -0,0 +1,232 @@
+use core::fmt;
+use core::ops::{ControlFlow, Range};
+
+use rkyv::rancor::{Fallible, Source};
+use rkyv::{Archive, Archived, Deserialize};
+
+use crate::query::{split, traverse, Subtree};
+use crate::validate::validate_with;
+use crate::{ArchivedITree, ArchivedNode, Entry, ITree, InvalidTree, Node};
+
+impl<K, V, S> ArchivedITree<K, V, S>
+where
+    K: Archive,
+    V: Archive,
+    S: Archive,
+    Archived<S>: AsRef<[ArchivedNode<K, V>]>,
+{
+    /// The number of items in the archived tree
+    pub fn len(&self) -> usize {
+        self.nodes.as_ref().len()
+    }
+
+    /// Whether the archived tree contains no items
+    pub fn is_empty(&self) -> bool {
+        self.nodes.as_ref().is_empty()
+    }
+
+    /// Iterate over all archived items in the order of their interval starts
+    pub fn iter(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = Entry<'_, Archived<K>, Archived<V>>> + ExactSizeIterator
+    {
+        self.nodes
+            .as_ref()
+            .iter()
+            .map(|node| Entry::new(&node.start, &node.end, &node.value))
+    }
+
+    /// Query the archived tree for all intervals overlapping the given interval without deserializing it
+    ///
+    /// Requires the `rkyv` feature. The bounds of the `interval` are given in their archived representation.
+    ///
+    /// Accessing an archive only verifies that its bytes are well-formed, not that the nodes are organized as an interval tree. Queries of an archive from an untrusted source can therefore yield wrong results unless it was [checked][Self::check] after accessing it.
+    pub fn query<'a, H>(&'a self, interval: Range<Archived<K>>, mut handler: H) -> ControlFlow<()>
+    where
+        Archived<K>: Ord,
+        H: FnMut(Entry<'a, Archived<K>, Archived<V>>) -> ControlFlow<()>,
+    {
+        traverse(&interval, self.nodes.as_ref(), &mut |node| {
+            handler(Entry::new(&node.start, &node.end, &node.value))
+        })
+    }
+
+    /// Verify that the archived nodes are organized as an interval tree, reporting the first violation
+    ///
+    /// Requires the `rkyv` feature and takes time linear in the number of nodes, like [`ITree::check`].
+    pub fn check(&self) -> Result<(), InvalidTree>
+    where
+        Archived<K>: Ord,
+    {
+        let nodes = self.nodes.as_ref();
+
+        validate_with(nodes.len(), |index| {
+            let node = &nodes[index];
+
+            (&node.start, &node.end, &node.max)
+        })
+    }
+
+    /// Whether the archived nodes are organized as an interval tree
+    pub fn is_valid(&self) -> bool
+    where
+        Archived<K>: Ord,
+    {
+        self.check().is_ok()
+    }
+}
+
+/// Deserializes an archived tree after verifying that its nodes are organized as an interval tree
+///
+/// Fails with an error created from [`InvalidTree`] otherwise.
+impl<K, V, S, D> Deserialize<ITree<K, V, S>, D> for ArchivedITree<K, V, S>
+where
+    K: Ord,
+    S: Archive + AsRef<[Node<K, V>]>,
+    Archived<S>: Deserialize<S, D>,
+    D: Fallible + ?Sized,
+    D::Error: Source,
+{
+    fn deserialize(&self, deserializer: &mut D) -> Result<ITree<K, V, S>, D::Error> {
+        let nodes = self.nodes.deserialize(deserializer)?;
+
+        ITree::from_nodes(nodes).map_err(D::Error::new)
+    }
+}
+
+impl<K, V> fmt::Debug for ArchivedNode<K, V>
+where
+    K: Archive,
+    V: Archive,
+    Archived<K>: fmt::Debug,
+    Archived<V>: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("ArchivedNode")
+            .field("start", &self.start)
+            .field("end", &self.end)
+            .field("max", &self.max)
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+impl<K, V, S> fmt::Debug for ArchivedITree<K, V, S>
+where
+    S: Archive,
+    Archived<S>: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("ArchivedITree")
+            .field("nodes", &self.nodes)
+            .finish()
+    }
+}
+
+impl<'a, K, V> Subtree for &'a [ArchivedNode<K, V>]
+where
+    K: Archive,
+    V: Archive,
+    Archived<K>: Ord,
+{
+    type Key = Archived<K>;
+    type Bound = &'a Archived<K>;
+    type Root = &'a ArchivedNode<K, V>;
+
+    fn len(self) -> usize {
+        <[_]>::len(self)
+    }
+
+    fn split(self) -> Option<(Self, Self::Root, Self)> {
+        split(self)
+    }
+
+    fn start(root: Self::Root) -> Self::Bound {
+        &root.start
+    }
+
+    fn end(root: Self::Root) -> Self::Bound {
+        &root.end
+    }
+
+    fn max(root: Self::Root) -> Self::Bound {
+        &root.max
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use proptest::{collection::vec, test_runner::TestRunner};
+    use rkyv::{access, deserialize, rancor::Error, rend::i32_le, to_bytes};
+
+    use crate::ITree;
+
+    #[test]
+    fn query_archived_random() {
+        const DOM: Range<i32> = -1000..1000;
+        const LEN: usize = 1000_usize;
+
+        TestRunner::default()
+            .run(
+                &(vec(DOM, LEN), vec(DOM, LEN), DOM, DOM),
+                |(start, end, query_start, query_end)| {
+                    let tree = ITree::<_, _>::new(
+                        start
+                            .iter()
+                            .zip(&end)
+                            .enumerate()
+                            .map(|(index, (&start, &end))| (start..end, index as u32)),
+                    );
+
+                    let mut result1 = Vec::new();
+                    let _ = tree.query(query_start..query_end, |entry| {
+                        result1.push(*entry.value());
+                        ControlFlow::Continue(())
+                    });
+
+                    let bytes = to_bytes::<Error>(&tree).unwrap();
+                    let archived = access::<ArchivedITree<i32, u32>, Error>(&bytes).unwrap();
+                    assert_eq!(archived.len(), LEN);
+
+                    let mut result2 = Vec::new();
+                    let _ = archived.query(
+                        i32_le::from_native(query_start)..i32_le::from_native(query_end),
+                        |entry| {
+                            result2.push(entry.value().to_native());
+                            ControlFlow::Continue(())
+                        },
+                    );
+
+                    assert_eq!(result1, result2);
+
+                    let tree2 = deserialize::<ITree<i32, u32>, Error>(archived).unwrap();
+                    assert_eq!(tree, tree2);
+
+                    Ok(())
+                },
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn check_archived_trees() {
+        let tree = ITree::<i32, u32>::new([(0..5, 0), (1..2, 1), (2..3, 2)]);
+
+        let bytes = to_bytes::<Error>(&tree).unwrap();
+        let archived = access::<ArchivedITree<i32, u32>, Error>(&bytes).unwrap();
+        assert!(archived.is_valid());
+
+        let mut nodes = tree.to_vec();
+        nodes[1].max = 3;
+        let tree = ITree::<i32, u32>::new_unchecked(nodes.into());
+
+        let bytes = to_bytes::<Error>(&tree).unwrap();
+        let archived = access::<ArchivedITree<i32, u32>, Error>(&bytes).unwrap();
+        assert_eq!(archived.check(), Err(InvalidTree::InvalidMax(1)));
+
+        let err = deserialize::<ITree<i32, u32>, Error>(archived).unwrap_err();
+        assert_eq!(err.to_string(), "node 1 has an invalid maximum end");
+    }
+}
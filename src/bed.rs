@@ -0,0 +1,194 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::io::{self, BufRead, Write};
+use std::ops::Range;
+
+use crate::{ITree, Node};
+
+/// A record of a BED file without its chromosome and coordinates
+///
+/// Requires the `bed` feature.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct BedRecord {
+    /// The name of the feature from the fourth column
+    pub name: Option<String>,
+    /// The score of the feature from the fifth column
+    pub score: Option<f64>,
+    /// The strand of the feature from the sixth column
+    pub strand: Option<Strand>,
+    /// Any further columns, e.g. the thick range, colour and blocks of the BED12 format
+    pub extra: Vec<String>,
+}
+
+/// The strand of a feature
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Strand {
+    /// The forward strand denoted by `+`
+    Forward,
+    /// The reverse strand denoted by `-`
+    Reverse,
+}
+
+impl ITree<u64, BedRecord> {
+    /// Read a BED file, constructing one tree per chromosome
+    ///
+    /// Requires the `bed` feature. BED coordinates are 0-based and half-open and are therefore used as interval bounds as-is. Empty lines, comments and `track` or `browser` lines are skipped. Optional columns are read if present, with `.` denoting missing values. Fails with [`InvalidData`][io::ErrorKind::InvalidData] naming the offending line if a record is malformed.
+    pub fn from_bed<R>(reader: R) -> io::Result<BTreeMap<String, Self>>
+    where
+        R: BufRead,
+    {
+        let mut items = BTreeMap::<String, Vec<_>>::new();
+
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+
+            if line.trim().is_empty()
+                || line.starts_with('#')
+                || line.starts_with("track")
+                || line.starts_with("browser")
+            {
+                continue;
+            }
+
+            let (chrom, item) = parse_line(&line).map_err(|err| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("line {}: {err}", index + 1),
+                )
+            })?;
+
+            match items.get_mut(chrom) {
+                Some(chrom_items) => chrom_items.push(item),
+                None => {
+                    items.insert(chrom.to_owned(), vec![item]);
+                }
+            }
+        }
+
+        Ok(items
+            .into_iter()
+            .map(|(chrom, items)| (chrom, Self::new(items)))
+            .collect())
+    }
+}
+
+impl<K, V, S> ITree<K, V, S>
+where
+    K: Ord + Clone + Display,
+    S: AsRef<[Node<K, V>]>,
+{
+    /// Write the [coverage][Self::coverage] of the tree as a bedGraph track for the given chromosome
+    ///
+    /// Requires the `bed` feature. Each line consists of the chromosome, the start and end of a range and its depth separated by tabs, so that this can also serve as a generic writer of values per disjoint range.
+    pub fn write_bedgraph<W>(&self, chrom: &str, mut writer: W) -> io::Result<W>
+    where
+        W: Write,
+    {
+        for (range, depth) in self.coverage() {
+            writeln!(writer, "{chrom}\t{}\t{}\t{depth}", range.start, range.end)?;
+        }
+
+        Ok(writer)
+    }
+}
+
+fn parse_line(line: &str) -> Result<(&str, (Range<u64>, BedRecord)), &'static str> {
+    let mut columns = line.split('\t');
+
+    let chrom = columns.next().ok_or("missing chromosome")?;
+
+    let start = columns
+        .next()
+        .ok_or("missing start")?
+        .trim()
+        .parse::<u64>()
+        .map_err(|_| "invalid start")?;
+
+    let end = columns
+        .next()
+        .ok_or("missing end")?
+        .trim()
+        .parse::<u64>()
+        .map_err(|_| "invalid end")?;
+
+    if start > end {
+        return Err("start after end");
+    }
+
+    let mut record = BedRecord::default();
+
+    if let Some(name) = columns.next().filter(|name| *name != ".") {
+        record.name = Some(name.to_owned());
+    }
+
+    if let Some(score) = columns.next().filter(|score| *score != ".") {
+        record.score = Some(score.trim().parse().map_err(|_| "invalid score")?);
+    }
+
+    if let Some(strand) = columns.next() {
+        record.strand = match strand.trim() {
+            "+" => Some(Strand::Forward),
+            "-" => Some(Strand::Reverse),
+            "." => None,
+            _ => return Err("invalid strand"),
+        };
+    }
+
+    record.extra = columns.map(ToOwned::to_owned).collect();
+
+    Ok((chrom, (start..end, record)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::ops::ControlFlow;
+
+    #[test]
+    fn read_bed_file() {
+        let bed = "\
+track name=genes
+# comment
+chr1\t100\t200\tgeneA\t960\t+
+chr1\t150\t300\tgeneB\t.\t-\t150\t300\t255,0,0
+
+chr2\t0\t50
+";
+
+        let trees = ITree::from_bed(bed.as_bytes()).unwrap();
+        assert_eq!(trees.keys().collect::<Vec<_>>(), ["chr1", "chr2"]);
+
+        let mut names = Vec::new();
+        let _ = trees["chr1"].query(180..190, |entry| {
+            names.push(entry.value().name.as_deref().unwrap());
+            ControlFlow::Continue(())
+        });
+        names.sort_unstable();
+        assert_eq!(names, ["geneA", "geneB"]);
+
+        let record = &trees["chr1"][1].value;
+        assert_eq!(record.score, None);
+        assert_eq!(record.strand, Some(Strand::Reverse));
+        assert_eq!(record.extra, ["150", "300", "255,0,0"]);
+
+        let node = &trees["chr2"][0];
+        assert_eq!(node.start..node.end, 0..50);
+        assert_eq!(node.value, BedRecord::default());
+
+        let err = ITree::from_bed("chr1\t100\tabc\n".as_bytes()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(err.to_string(), "line 1: invalid end");
+    }
+
+    #[test]
+    fn write_bedgraph_track() {
+        let trees = ITree::from_bed("chr1\t0\t10\nchr1\t5\t20\n".as_bytes()).unwrap();
+
+        let buf = trees["chr1"].write_bedgraph("chr1", Vec::new()).unwrap();
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "chr1\t0\t5\t1\nchr1\t5\t10\t2\nchr1\t10\t20\t1\n"
+        );
+    }
+}
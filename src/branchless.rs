@@ -0,0 +1,110 @@
+use core::hint::select_unpredictable;
+use core::ops::{ControlFlow, Range};
+
+use crate::query::QueryArgs;
+use crate::{Entry, ITree, Node, PrimitiveKey};
+
+impl<K, V, S> ITree<K, V, S>
+where
+    K: PrimitiveKey,
+    S: AsRef<[Node<K, V>]>,
+{
+    /// Query for all intervals overlapping the given interval, selecting the next subtree arithmetically instead of branching
+    ///
+    /// Besides reporting an item, only descending into both subtrees requires a branch, which reduces mispredictions for random queries. Yields the same items as [`query`][Self::query].
+    pub fn query_branchless<'a, H>(&'a self, interval: Range<K>, handler: H) -> ControlFlow<()>
+    where
+        H: FnMut(Entry<'a, K, V>) -> ControlFlow<()>,
+    {
+        let nodes = self.nodes.as_ref();
+
+        if !nodes.is_empty() {
+            query(&mut QueryArgs { interval, handler }, nodes)?;
+        }
+
+        ControlFlow::Continue(())
+    }
+}
+
+fn query<'a, K, V, H>(args: &mut QueryArgs<K, H>, mut nodes: &'a [Node<K, V>]) -> ControlFlow<()>
+where
+    K: PrimitiveKey,
+    H: FnMut(Entry<'a, K, V>) -> ControlFlow<()>,
+{
+    let Range { start, end } = args.interval;
+
+    while !nodes.is_empty() {
+        let len = nodes.len();
+        let mid = len / 2;
+        let node = &nodes[mid];
+
+        let below_max = start < node.max;
+        let after_start = below_max & (end > node.start);
+
+        let go_left = below_max & (mid != 0);
+        let go_right = after_start & (mid + 1 != len);
+
+        if after_start & (start < node.end) {
+            (args.handler)(node.into())?;
+        }
+
+        if go_left & go_right {
+            query(args, &nodes[..mid])?;
+        }
+
+        // Continue with the right subtree if it overlaps, otherwise with the left one if it does, otherwise with nothing.
+        let (offset, len) = select_unpredictable(
+            go_right,
+            (mid + 1, len - mid - 1),
+            (0, select_unpredictable(go_left, mid, 0)),
+        );
+
+        nodes = &nodes[offset..offset + len];
+    }
+
+    ControlFlow::Continue(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use proptest::{collection::vec, test_runner::TestRunner};
+
+    #[test]
+    fn query_branchless_random() {
+        const DOM: Range<i32> = -1000..1000;
+        const LEN: usize = 1000_usize;
+
+        TestRunner::default()
+            .run(
+                &(vec(DOM, LEN), vec(DOM, LEN), DOM, DOM),
+                |(start, end, query_start, query_end)| {
+                    let tree = ITree::<_, _>::new(
+                        start
+                            .iter()
+                            .zip(&end)
+                            .enumerate()
+                            .map(|(index, (&start, &end))| (start..end, index)),
+                    );
+
+                    let mut result1 = Vec::new();
+                    let _ = tree.query_branchless(query_start..query_end, |entry| {
+                        result1.push(*entry.value());
+                        ControlFlow::Continue(())
+                    });
+
+                    let mut result2 = Vec::new();
+                    let _ = tree.query(query_start..query_end, |entry| {
+                        result2.push(*entry.value());
+                        ControlFlow::Continue(())
+                    });
+
+                    assert_eq!(result1, result2);
+
+                    Ok(())
+                },
+            )
+            .unwrap()
+    }
+}
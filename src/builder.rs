@@ -0,0 +1,131 @@
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use crate::sort::{node, sort, update_max};
+#[cfg(feature = "rayon")]
+use crate::sort::{par_sort, par_update_max};
+use crate::{ITree, Item, Node};
+
+/// Incrementally collects items before constructing a tree
+#[derive(Debug, Clone)]
+pub struct ITreeBuilder<K, V> {
+    nodes: Vec<Node<K, V>>,
+}
+
+impl<K, V> Default for ITreeBuilder<K, V> {
+    fn default() -> Self {
+        Self { nodes: Vec::new() }
+    }
+}
+
+impl<K, V> ITreeBuilder<K, V>
+where
+    K: Ord + Clone,
+{
+    /// Create an empty builder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an empty builder with space for at least `capacity` items
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            nodes: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Reserve space for at least `additional` more items
+    pub fn reserve(&mut self, additional: usize) {
+        self.nodes.reserve(additional);
+    }
+
+    /// Add a single item
+    pub fn push(&mut self, item: Item<K, V>) {
+        self.nodes.push(node(item));
+    }
+
+    /// Number of items collected so far
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Whether no items were collected so far
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Construct a new tree by sorting the collected items
+    pub fn build<S>(mut self) -> ITree<K, V, S>
+    where
+        S: From<Vec<Node<K, V>>>,
+    {
+        sort(&mut self.nodes);
+
+        if !self.nodes.is_empty() {
+            update_max(&mut self.nodes);
+        }
+
+        ITree {
+            nodes: self.nodes.into(),
+            _marker: PhantomData,
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    /// Construct a new tree by sorting the collected items, in parallel
+    ///
+    /// Requires the `rayon` feature and dispatches tasks into the current [thread pool][rayon::ThreadPool].
+    pub fn par_build<S>(mut self) -> ITree<K, V, S>
+    where
+        K: Send,
+        V: Send,
+        S: From<Vec<Node<K, V>>>,
+    {
+        par_sort(&mut self.nodes);
+
+        if !self.nodes.is_empty() {
+            par_update_max(&mut self.nodes);
+        }
+
+        ITree {
+            nodes: self.nodes.into(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<K, V> Extend<Item<K, V>> for ITreeBuilder<K, V>
+where
+    K: Ord + Clone,
+{
+    fn extend<I>(&mut self, items: I)
+    where
+        I: IntoIterator<Item = Item<K, V>>,
+    {
+        let items = items.into_iter();
+        self.nodes.reserve(items.size_hint().0);
+
+        for item in items {
+            self.push(item);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_from_several_sources() {
+        let mut builder = ITreeBuilder::with_capacity(3);
+
+        builder.push((5..10, 'a'));
+        builder.extend([(0..3, 'b'), (2..8, 'c')]);
+
+        assert_eq!(builder.len(), 3);
+
+        let tree: ITree<_, _> = builder.build();
+
+        assert!(tree.iter().eq(&[(0..3, 'b'), (2..8, 'c'), (5..10, 'a')]));
+    }
+}
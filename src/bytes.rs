@@ -0,0 +1,234 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt;
+use core::mem::{offset_of, size_of, size_of_val};
+use core::slice::from_raw_parts;
+
+use bytemuck::{bytes_of, try_cast_slice, AnyBitPattern, NoUninit, PodCastError};
+
+use crate::{ITree, InvalidTree, Node};
+
+impl<K, V, S> ITree<K, V, S>
+where
+    K: NoUninit,
+    V: NoUninit,
+    S: AsRef<[Node<K, V>]>,
+{
+    /// The nodes in their native `#[repr(C)]` layout with zeroed padding
+    ///
+    /// Requires the `bytemuck` feature. The result can be embedded into a binary and loaded using [`from_pod_bytes`][ITree::from_pod_bytes] on a target with the same endianness.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let nodes = self.nodes.as_ref();
+
+        if let Some(bytes) = padding_free_bytes(nodes) {
+            return bytes.to_vec();
+        }
+
+        let mut bytes = vec![0; size_of_val(nodes)];
+
+        copy_fields(nodes, &mut bytes);
+
+        bytes
+    }
+}
+
+/// The bytes of `nodes` if their layout contains no padding so that they can be copied in bulk
+pub(crate) fn padding_free_bytes<K, V>(nodes: &[Node<K, V>]) -> Option<&[u8]>
+where
+    K: NoUninit,
+    V: NoUninit,
+{
+    if size_of::<Node<K, V>>() != 3 * size_of::<K>() + size_of::<V>() {
+        return None;
+    }
+
+    #[allow(unsafe_code)]
+    // SAFETY: As `K` and `V` are `NoUninit` and `Node<K, V>` contains no padding, all bytes of `nodes` are initialized.
+    let bytes = unsafe { from_raw_parts(nodes.as_ptr().cast::<u8>(), size_of_val(nodes)) };
+
+    Some(bytes)
+}
+
+/// Copies `nodes` into `bytes` field by field, leaving their padding zeroed
+pub(crate) fn copy_fields<K, V>(nodes: &[Node<K, V>], bytes: &mut [u8])
+where
+    K: NoUninit,
+    V: NoUninit,
+{
+    for (node, bytes) in nodes
+        .iter()
+        .zip(bytes.chunks_exact_mut(size_of::<Node<K, V>>()))
+    {
+        let mut put = |offset, field: &[u8]| {
+            bytes[offset..offset + field.len()].copy_from_slice(field);
+        };
+
+        put(offset_of!(Node<K, V>, start), bytes_of(&node.start));
+        put(offset_of!(Node<K, V>, end), bytes_of(&node.end));
+        put(offset_of!(Node<K, V>, max), bytes_of(&node.max));
+        put(offset_of!(Node<K, V>, value), bytes_of(&node.value));
+    }
+}
+
+impl<'a, K, V> ITree<K, V, &'a [Node<K, V>]>
+where
+    K: Ord + AnyBitPattern,
+    V: AnyBitPattern,
+{
+    /// Interprets the given `bytes` as nodes in their native `#[repr(C)]` layout without copying them
+    ///
+    /// Requires the `bytemuck` feature. Bytes produced by [`to_bytes`][ITree::to_bytes] can be embedded into a binary via `include_bytes!` and aligned using [`AlignedBytes`]. Fails if the `bytes` are not aligned for `Node<K, V>`, if their length is not a multiple of its size or if the nodes are not organized as an interval tree. [`Pod`][bytemuck::Pod] is not implemented for [`Node`] as its padding depends on `K` and `V`, but [`AnyBitPattern`] suffices for reading.
+    pub fn from_pod_bytes(bytes: &'a [u8]) -> Result<Self, InvalidBytes> {
+        let nodes = try_cast_slice(bytes).map_err(|err| match err {
+            PodCastError::TargetAlignmentGreaterAndInputNotAligned => InvalidBytes::Unaligned,
+            _ => InvalidBytes::Truncated,
+        })?;
+
+        Self::from_nodes(nodes).map_err(InvalidBytes::InvalidTree)
+    }
+}
+
+impl<K, V> ITree<K, V, &'static [Node<K, V>]>
+where
+    K: Ord + AnyBitPattern,
+    V: AnyBitPattern,
+{
+    /// Interprets bytes embedded into the binary, e.g. via `include_bytes!`, as a tree without copying them
+    ///
+    /// Requires the `bytemuck` feature. Performs the same checks as [`from_pod_bytes`][ITree::from_pod_bytes], so the `bytes` must have been produced by [`to_bytes`][ITree::to_bytes] and be aligned using [`AlignedBytes`].
+    pub fn from_static_bytes(bytes: &'static [u8]) -> Result<Self, InvalidBytes> {
+        Self::from_pod_bytes(bytes)
+    }
+}
+
+/// Bytes aligned suitably for interpreting them as `Node<K, V>`
+///
+/// Allows embedding trees into a binary via `static BYTES: &AlignedBytes<K, V> = &AlignedBytes::new(*include_bytes!("tree.bin"));` and loading them using [`ITree::from_static_bytes(&BYTES.bytes)`][ITree::from_static_bytes].
+#[derive(Debug)]
+#[repr(C)]
+pub struct AlignedBytes<K, V, B: ?Sized = [u8]> {
+    _align: [Node<K, V>; 0],
+    /// The aligned bytes
+    pub bytes: B,
+}
+
+impl<K, V, B> AlignedBytes<K, V, B> {
+    /// Align the given `bytes`
+    pub const fn new(bytes: B) -> Self {
+        Self { _align: [], bytes }
+    }
+}
+
+/// Error indicating that bytes cannot be interpreted as a tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidBytes {
+    /// The bytes are not aligned suitably for the nodes
+    Unaligned,
+    /// The length of the bytes is not a multiple of the node size or smaller than announced by the header
+    Truncated,
+    /// The header is invalid or does not match the key and value types
+    InvalidHeader,
+    /// The checksum does not match the contents
+    ChecksumMismatch,
+    /// The nodes are not organized as an interval tree
+    InvalidTree(InvalidTree),
+}
+
+impl fmt::Display for InvalidBytes {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unaligned => write!(fmt, "bytes are not aligned for nodes"),
+            Self::Truncated => write!(fmt, "bytes are truncated"),
+            Self::InvalidHeader => write!(fmt, "invalid header"),
+            Self::ChecksumMismatch => write!(fmt, "checksum mismatch"),
+            Self::InvalidTree(err) => write!(fmt, "invalid tree: {err}"),
+        }
+    }
+}
+
+impl Error for InvalidBytes {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::InvalidTree(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn static_bytes_of_embedded_tree() {
+        const fn ne_bytes(words: [u32; 8]) -> [u8; 32] {
+            let mut bytes = [0; 32];
+
+            let mut index = 0;
+
+            while index < bytes.len() {
+                bytes[index] = words[index / 4].to_ne_bytes()[index % 4];
+
+                index += 1;
+            }
+
+            bytes
+        }
+
+        static BYTES: &AlignedBytes<u32, u32> =
+            &AlignedBytes::new(ne_bytes([0, 5, 5, 7, 1, 2, 5, 9]));
+
+        let tree = ITree::<u32, u32, _>::from_static_bytes(&BYTES.bytes).unwrap();
+        assert_eq!(tree, ITree::<_, _>::new([(0..5, 7), (1..2, 9)]));
+        assert_eq!(tree.to_bytes(), BYTES.bytes);
+
+        assert_eq!(
+            ITree::<u32, u32, _>::from_static_bytes(&BYTES.bytes[16..]).unwrap_err(),
+            InvalidBytes::InvalidTree(InvalidTree::InvalidMax(0))
+        );
+    }
+
+    #[test]
+    fn pod_bytes_roundtrip() {
+        let tree = ITree::<u16, u64>::new([(0..5, 0), (1..2, 1), (2..3, 2)]);
+
+        let bytes = tree.to_bytes();
+        assert_eq!(bytes.len(), 3 * size_of::<Node<u16, u64>>());
+
+        let mut aligned = AlignedBytes::<u16, u64, _>::new([0; 48]);
+        aligned.bytes.copy_from_slice(&bytes);
+
+        let loaded = ITree::<u16, u64, _>::from_pod_bytes(&aligned.bytes).unwrap();
+        assert_eq!(loaded.as_inner(), &&*tree.nodes);
+
+        assert_eq!(
+            ITree::<u16, u64, _>::from_pod_bytes(&aligned.bytes[1..17]).unwrap_err(),
+            InvalidBytes::Unaligned
+        );
+        assert_eq!(
+            ITree::<u16, u64, _>::from_pod_bytes(&aligned.bytes[..40]).unwrap_err(),
+            InvalidBytes::Truncated
+        );
+        assert_eq!(
+            ITree::<u16, u64, _>::from_pod_bytes(&aligned.bytes[16..]).unwrap_err(),
+            InvalidBytes::InvalidTree(InvalidTree::InvalidMax(0))
+        );
+    }
+
+    #[test]
+    fn pod_bytes_of_local_buffer() {
+        let tree = ITree::<u32, i32>::new([(0..5, -1), (1..2, -2)]);
+
+        let mut aligned = AlignedBytes::<u32, i32, _>::new([0; 32]);
+        aligned.bytes.copy_from_slice(&tree.to_bytes());
+
+        let loaded = ITree::<u32, i32, _>::from_pod_bytes(&aligned.bytes).unwrap();
+        assert_eq!(loaded, tree);
+
+        assert_eq!(
+            ITree::<u32, i32, _>::from_pod_bytes(&aligned.bytes[2..18]).unwrap_err(),
+            InvalidBytes::Unaligned
+        );
+    }
+}
@@ -0,0 +1,63 @@
+use std::hint::black_box;
+use std::ops::{ControlFlow, Range};
+use std::time::{Duration, Instant};
+
+use crate::{ITree, Node};
+
+const BUCKET_LENS: [usize; 8] = [0, 4, 8, 16, 32, 64, 128, 256];
+
+impl<K, V, S> ITree<K, V, S>
+where
+    K: Ord + Clone,
+    S: AsRef<[Node<K, V>]>,
+{
+    /// Measure which bucket length makes [`query_bucketed`][Self::query_bucketed] fastest for the given sample of `queries`
+    ///
+    /// Requires the `std` feature. Each candidate is timed by running all `queries` against this tree, so the sample should be representative of the actual workload. A length of zero disables the linear scan.
+    pub fn calibrate_bucket_len(&self, queries: &[Range<K>]) -> usize {
+        let mut best = (Duration::MAX, 0);
+
+        for bucket_len in BUCKET_LENS {
+            let started = Instant::now();
+
+            for query in queries {
+                let _ = self.query_bucketed(query.clone(), bucket_len, |entry| {
+                    black_box(entry);
+                    ControlFlow::Continue(())
+                });
+            }
+
+            let elapsed = started.elapsed();
+
+            if elapsed < best.0 {
+                best = (elapsed, bucket_len);
+            }
+
+            if bucket_len >= self.len() {
+                break;
+            }
+        }
+
+        best.1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn calibrate_bucket_len_picks_candidate() {
+        let tree = ITree::<_, _>::new((0..10_000).map(|start| (start..start + 10, ())));
+
+        let queries = (0..100)
+            .map(|start| start * 100..start * 100 + 5)
+            .collect::<Vec<_>>();
+
+        let bucket_len = tree.calibrate_bucket_len(&queries);
+        assert!(BUCKET_LENS.contains(&bucket_len));
+
+        let tree = ITree::<_, _>::new([(0..1, ())]);
+        assert!([0, 4].contains(&tree.calibrate_bucket_len(&[0..1, 1..2])));
+    }
+}
@@ -0,0 +1,188 @@
+use core::ops::{ControlFlow, Range};
+
+use crate::query::{Entry, QueryArgs};
+use crate::{ITree, InvalidTree, Node};
+
+impl<K, V, S> ITree<K, V, S>
+where
+    S: AsRef<[Node<K, V>]>,
+{
+    /// Query for all intervals overlapping the given interval, verifying the invariants of each visited node
+    ///
+    /// Intended for trees backed by untrusted bytes which were not [checked][Self::check] up front. Each visited node must start within the bounds implied by its ancestors and store the maximum of its own end and those of its children. Fails with the first violation instead of yielding wrong results, but items passed to the `handler` before the violation was detected are not retracted. Only visited nodes are verified, so this cannot detect corruption of subtrees which are pruned based on corrupted maxima.
+    pub fn query_checked<'a, H>(
+        &'a self,
+        interval: Range<K>,
+        handler: H,
+    ) -> Result<ControlFlow<()>, InvalidTree>
+    where
+        K: Ord,
+        H: FnMut(Entry<'a, K, V>) -> ControlFlow<()>,
+    {
+        let nodes = self.nodes.as_ref();
+
+        if !nodes.is_empty() {
+            let bounds = Bounds {
+                lower: None,
+                upper: None,
+            };
+
+            match query_checked(&mut QueryArgs { interval, handler }, nodes, 0, bounds) {
+                ControlFlow::Break(None) => return Ok(ControlFlow::Break(())),
+                ControlFlow::Break(Some(err)) => return Err(err),
+                ControlFlow::Continue(()) => (),
+            }
+        }
+
+        Ok(ControlFlow::Continue(()))
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Bounds<'a, K> {
+    lower: Option<&'a K>,
+    upper: Option<&'a K>,
+}
+
+fn query_checked<'a, K, V, H>(
+    args: &mut QueryArgs<K, H>,
+    nodes: &'a [Node<K, V>],
+    offset: usize,
+    bounds: Bounds<'a, K>,
+) -> ControlFlow<Option<InvalidTree>>
+where
+    K: Ord,
+    H: FnMut(Entry<'a, K, V>) -> ControlFlow<()>,
+{
+    let (left, [mid, right @ ..]) = nodes.split_at(nodes.len() / 2) else {
+        unreachable!()
+    };
+
+    let index = offset + left.len();
+
+    if bounds.lower.is_some_and(|lower| mid.start < *lower)
+        || bounds.upper.is_some_and(|upper| mid.start > *upper)
+    {
+        return ControlFlow::Break(Some(InvalidTree::Unsorted(index)));
+    }
+
+    let mut max = &mid.end;
+
+    for child in [left, right] {
+        if !child.is_empty() {
+            max = max.max(&child[child.len() / 2].max);
+        }
+    }
+
+    if mid.max != *max {
+        return ControlFlow::Break(Some(InvalidTree::InvalidMax(index)));
+    }
+
+    if args.interval.start < mid.max {
+        if !left.is_empty() {
+            let bounds = Bounds {
+                upper: Some(&mid.start),
+                ..bounds
+            };
+
+            query_checked(args, left, offset, bounds)?;
+        }
+
+        if args.interval.end > mid.start {
+            if args.interval.start < mid.end {
+                (args.handler)(mid.into()).map_break(|()| None)?;
+            }
+
+            if !right.is_empty() {
+                let bounds = Bounds {
+                    lower: Some(&mid.start),
+                    ..bounds
+                };
+
+                query_checked(args, right, index + 1, bounds)?;
+            }
+        }
+    }
+
+    ControlFlow::Continue(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::marker::PhantomData;
+
+    use proptest::{collection::vec, test_runner::TestRunner};
+
+    #[test]
+    fn checked_query_matches_query() {
+        TestRunner::default()
+            .run(
+                &(vec((0_i32..100, 0_i32..10), 0..100), 0_i32..100, 0_i32..20),
+                |(items, start, len)| {
+                    let tree = ITree::<_, _>::new(
+                        items
+                            .iter()
+                            .enumerate()
+                            .map(|(idx, &(start, len))| (start..start + len, idx)),
+                    );
+
+                    let mut expected = Vec::new();
+                    let _ = tree.query(start..start + len, |entry| {
+                        expected.push(*entry.value());
+                        ControlFlow::Continue(())
+                    });
+
+                    let mut actual = Vec::new();
+                    let res = tree.query_checked(start..start + len, |entry| {
+                        actual.push(*entry.value());
+                        ControlFlow::Continue(())
+                    });
+
+                    assert_eq!(res, Ok(ControlFlow::Continue(())));
+
+                    expected.sort_unstable();
+                    actual.sort_unstable();
+                    assert_eq!(expected, actual);
+
+                    Ok(())
+                },
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn checked_query_detects_corruption() {
+        let tree = ITree::<_, _>::new([(0..5, ()), (1..2, ()), (2..3, ())]);
+
+        let mut nodes = tree.to_vec();
+        nodes[1].max = 3;
+        let corrupted = ITree::<_, _, _> {
+            nodes,
+            _marker: PhantomData,
+        };
+        assert_eq!(
+            corrupted.query_checked(0..10, |_| ControlFlow::Continue(())),
+            Err(InvalidTree::InvalidMax(1))
+        );
+
+        let mut nodes = tree.to_vec();
+        nodes[2].start = 0;
+        nodes[2].end = 1;
+        nodes[2].max = 1;
+        let corrupted = ITree::<_, _, _> {
+            nodes,
+            _marker: PhantomData,
+        };
+        assert_eq!(
+            corrupted.query_checked(0..10, |_| ControlFlow::Continue(())),
+            Err(InvalidTree::Unsorted(2))
+        );
+
+        assert_eq!(
+            tree.query_checked(0..10, |_| ControlFlow::Break(())),
+            Ok(ControlFlow::Break(()))
+        );
+    }
+}
@@ -0,0 +1,113 @@
+#[cfg(feature = "external")]
+use crate::Item;
+
+/// Fixed-size binary encoding of keys and values
+///
+/// Implemented for the primitive numeric types using their little-endian representation and for `()` using zero bytes.
+pub trait Codec: Sized {
+    /// The number of bytes of the encoded representation
+    const SIZE: usize;
+
+    /// Identifies the type in the header of the binary format, zero meaning that the type is not known to this crate
+    ///
+    /// Types with tag zero cannot be written or read using the binary format as different types could not be told apart. Types defined outside of this crate should use tags of at least 128 which are never assigned to types of this crate.
+    const TAG: u8 = 0;
+
+    /// Encode `self` into the first [`SIZE`][Self::SIZE] bytes of `buf`
+    fn encode(&self, buf: &mut [u8]);
+
+    /// Decode a value from the first [`SIZE`][Self::SIZE] bytes of `buf`
+    fn decode(buf: &[u8]) -> Self;
+}
+
+macro_rules! impl_codec {
+    ($($type:ty => $tag:literal),*) => {
+        $(
+            impl Codec for $type {
+                const SIZE: usize = core::mem::size_of::<$type>();
+
+                const TAG: u8 = $tag;
+
+                fn encode(&self, buf: &mut [u8]) {
+                    buf[..Self::SIZE].copy_from_slice(&self.to_le_bytes());
+                }
+
+                fn decode(buf: &[u8]) -> Self {
+                    Self::from_le_bytes(buf[..Self::SIZE].try_into().unwrap())
+                }
+            }
+        )*
+    };
+}
+
+impl_codec!(
+    u8 => 1,
+    u16 => 2,
+    u32 => 3,
+    u64 => 4,
+    u128 => 5,
+    i8 => 6,
+    i16 => 7,
+    i32 => 8,
+    i64 => 9,
+    i128 => 10,
+    f32 => 11,
+    f64 => 12
+);
+
+impl Codec for () {
+    const SIZE: usize = 0;
+
+    const TAG: u8 = 13;
+
+    fn encode(&self, _buf: &mut [u8]) {}
+
+    fn decode(_buf: &[u8]) -> Self {}
+}
+
+impl Codec for bool {
+    const SIZE: usize = 1;
+
+    const TAG: u8 = 14;
+
+    fn encode(&self, buf: &mut [u8]) {
+        buf[0] = *self as u8;
+    }
+
+    fn decode(buf: &[u8]) -> Self {
+        buf[0] != 0
+    }
+}
+
+#[cfg(feature = "external")]
+pub(crate) fn item_size<K, V>() -> usize
+where
+    K: Codec,
+    V: Codec,
+{
+    2 * K::SIZE + V::SIZE
+}
+
+#[cfg(feature = "external")]
+pub(crate) fn encode_item<K, V>((interval, value): &Item<K, V>, buf: &mut [u8])
+where
+    K: Codec,
+    V: Codec,
+{
+    interval.start.encode(buf);
+    interval.end.encode(&mut buf[K::SIZE..]);
+    value.encode(&mut buf[2 * K::SIZE..]);
+}
+
+#[cfg(feature = "external")]
+pub(crate) fn decode_item<K, V>(buf: &[u8]) -> Item<K, V>
+where
+    K: Codec,
+    V: Codec,
+{
+    let start = K::decode(buf);
+    let end = K::decode(&buf[K::SIZE..]);
+    let value = V::decode(&buf[2 * K::SIZE..]);
+
+    (start..end, value)
+}
@@ -0,0 +1,186 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt;
+
+use crate::{Codec, ITree, Node, PrimitiveKey};
+
+impl<K, V, S> ITree<K, V, S>
+where
+    K: PrimitiveKey,
+    V: Codec,
+    S: AsRef<[Node<K, V>]>,
+{
+    /// Encode the items into a compact, self-delimiting byte representation
+    ///
+    /// Requires the `compact` feature. Interval starts are delta-encoded and interval lengths are stored as variable-length integers whereas values use their [`Codec`]. The maximum of the interval upper bounds is not stored but recomputed by [`decode_compact`][Self::decode_compact].
+    pub fn encode_compact(&self) -> Vec<u8> {
+        let nodes = self.nodes.as_ref();
+
+        let mut bytes = Vec::new();
+        let mut value = vec![0; V::SIZE];
+
+        put_varint(&mut bytes, nodes.len() as u128);
+
+        let mut prev = 0;
+
+        for node in nodes {
+            let start = node.start.to_i128();
+            let end = node.end.to_i128();
+
+            put_varint(&mut bytes, zigzag(start - prev));
+            put_varint(&mut bytes, zigzag(end - start));
+
+            node.value.encode(&mut value);
+            bytes.extend_from_slice(&value);
+
+            prev = start;
+        }
+
+        bytes
+    }
+}
+
+impl<K, V, S> ITree<K, V, S>
+where
+    K: PrimitiveKey,
+    V: Codec,
+    S: AsMut<[Node<K, V>]> + FromIterator<Node<K, V>>,
+{
+    /// Decode a tree produced by [`encode_compact`][Self::encode_compact], returning it together with the remaining bytes
+    ///
+    /// Requires the `compact` feature. Fails if the `bytes` are truncated or if the intervals do not fit into `K`.
+    pub fn decode_compact(mut bytes: &[u8]) -> Result<(Self, &[u8]), InvalidEncoding> {
+        let len = take_varint(&mut bytes)?;
+        let len = usize::try_from(len).map_err(|_err| InvalidEncoding)?;
+
+        let mut items = Vec::with_capacity(len.min(bytes.len()));
+
+        let mut prev = 0_i128;
+
+        for _ in 0..len {
+            let start = prev
+                .checked_add(unzigzag(take_varint(&mut bytes)?))
+                .ok_or(InvalidEncoding)?;
+            let end = start
+                .checked_add(unzigzag(take_varint(&mut bytes)?))
+                .ok_or(InvalidEncoding)?;
+
+            if bytes.len() < V::SIZE {
+                return Err(InvalidEncoding);
+            }
+
+            let (value, rest) = bytes.split_at(V::SIZE);
+            bytes = rest;
+
+            let start_key = K::from_i128(start).ok_or(InvalidEncoding)?;
+            let end_key = K::from_i128(end).ok_or(InvalidEncoding)?;
+
+            items.push((start_key..end_key, V::decode(value)));
+
+            prev = start;
+        }
+
+        Ok((Self::new(items), bytes))
+    }
+}
+
+/// Error indicating that bytes are not a valid compact encoding of a tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidEncoding;
+
+impl fmt::Display for InvalidEncoding {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "invalid compact encoding")
+    }
+}
+
+impl Error for InvalidEncoding {}
+
+fn zigzag(value: i128) -> u128 {
+    ((value << 1) ^ (value >> 127)) as u128
+}
+
+fn unzigzag(value: u128) -> i128 {
+    ((value >> 1) as i128) ^ -((value & 1) as i128)
+}
+
+fn put_varint(bytes: &mut Vec<u8>, mut value: u128) {
+    while value >= 0x80 {
+        bytes.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+
+    bytes.push(value as u8);
+}
+
+fn take_varint(bytes: &mut &[u8]) -> Result<u128, InvalidEncoding> {
+    let mut value = 0;
+
+    for (index, &byte) in bytes.iter().enumerate() {
+        let shift = 7 * index as u32;
+
+        if shift >= 128 || (shift > 121 && byte >> (128 - shift) != 0) {
+            return Err(InvalidEncoding);
+        }
+
+        value |= u128::from(byte & 0x7f) << shift;
+
+        if byte & 0x80 == 0 {
+            *bytes = &bytes[index + 1..];
+
+            return Ok(value);
+        }
+    }
+
+    Err(InvalidEncoding)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use proptest::{arbitrary::any, collection::vec, test_runner::TestRunner};
+
+    #[test]
+    fn compact_roundtrip() {
+        TestRunner::default()
+            .run(
+                &vec((any::<i64>(), any::<i64>(), any::<u16>()), 0..100),
+                |items| {
+                    let tree = ITree::<_, _>::new(
+                        items
+                            .into_iter()
+                            .map(|(start, end, value)| (start..end, value)),
+                    );
+
+                    let mut bytes = tree.encode_compact();
+                    bytes.push(42);
+
+                    let (decoded, rest) = ITree::<_, _>::decode_compact(&bytes).unwrap();
+                    assert_eq!(decoded.as_inner(), tree.as_inner());
+                    assert_eq!(rest, [42]);
+
+                    for len in 0..bytes.len() - 1 {
+                        ITree::<i64, u16>::decode_compact(&bytes[..len]).unwrap_err();
+                    }
+
+                    Ok(())
+                },
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn compact_rejects_out_of_range_keys() {
+        let tree = ITree::<u32, ()>::new([(0..300, ())]);
+
+        let bytes = tree.encode_compact();
+        assert_eq!(bytes.len(), 4);
+
+        assert_eq!(
+            ITree::<u8, ()>::decode_compact(&bytes).unwrap_err(),
+            InvalidEncoding
+        );
+    }
+}
@@ -0,0 +1,325 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt;
+use core::ops::{ControlFlow, Range};
+
+#[cfg(feature = "serde")]
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize};
+
+use crate::query::{split, traverse, Subtree};
+use crate::{ITree, Node};
+
+const BLOCK_LEN: usize = 64;
+
+/// Interval tree with `u64` bounds stored as `u32` offsets which are decoded during traversal
+///
+/// The starts are stored relative to the first start of each block of 64 nodes and the ends relative to their starts.
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct CompressedITree<V> {
+    bases: Box<[u64]>,
+    nodes: Box<[CompressedNode<V>]>,
+}
+
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct CompressedNode<V> {
+    start: u32,
+    end: u32,
+    max: u64,
+    value: V,
+}
+
+impl<V> CompressedITree<V> {
+    /// The number of items in the tree
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Whether the tree contains no items
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Iterate over all items in the order of their interval starts
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (Range<u64>, &V)> + ExactSizeIterator {
+        (0..self.nodes.len()).map(|index| self.decode(index))
+    }
+
+    /// Query for all intervals overlapping the given interval
+    ///
+    /// The `handler` is passed the decoded interval and a reference to the value.
+    pub fn query<'a, H>(&'a self, interval: Range<u64>, mut handler: H) -> ControlFlow<()>
+    where
+        H: FnMut(Range<u64>, &'a V) -> ControlFlow<()>,
+    {
+        let nodes = Subslice {
+            bases: &self.bases,
+            nodes: &self.nodes,
+            offset: 0,
+        };
+
+        traverse(&interval, nodes, &mut |root| {
+            handler(Subslice::start(root)..Subslice::end(root), &root.1.value)
+        })
+    }
+
+    fn decode(&self, index: usize) -> (Range<u64>, &V) {
+        let node = &self.nodes[index];
+
+        let start = self.bases[index / BLOCK_LEN] + u64::from(node.start);
+        let end = start + u64::from(node.end);
+
+        (start..end, &node.value)
+    }
+}
+
+/// The nodes of a subtree together with the position of its first node, to look up the bases of their blocks
+struct Subslice<'a, V> {
+    bases: &'a [u64],
+    nodes: &'a [CompressedNode<V>],
+    offset: usize,
+}
+
+impl<V> Clone for Subslice<'_, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<V> Copy for Subslice<'_, V> {}
+
+impl<'a, V> Subtree for Subslice<'a, V> {
+    type Key = u64;
+    type Bound = u64;
+    type Root = (u64, &'a CompressedNode<V>);
+
+    fn len(self) -> usize {
+        self.nodes.len()
+    }
+
+    fn split(self) -> Option<(Self, Self::Root, Self)> {
+        let (left, mid, right) = split(self.nodes)?;
+
+        let index = self.offset + left.len();
+        let base = self.bases[index / BLOCK_LEN];
+
+        let left = Self {
+            nodes: left,
+            ..self
+        };
+
+        let right = Self {
+            nodes: right,
+            offset: index + 1,
+            ..self
+        };
+
+        Some((left, (base, mid), right))
+    }
+
+    fn start((base, node): Self::Root) -> Self::Bound {
+        base + u64::from(node.start)
+    }
+
+    fn end(root: Self::Root) -> Self::Bound {
+        Self::start(root) + u64::from(root.1.end)
+    }
+
+    fn max((_base, node): Self::Root) -> Self::Bound {
+        node.max
+    }
+}
+
+/// Compresses the bounds of the given tree, failing if an offset does not fit into 32 bits or an interval is inverted
+impl<V, S> TryFrom<ITree<u64, V, S>> for CompressedITree<V>
+where
+    S: Into<Vec<Node<u64, V>>>,
+{
+    type Error = OffsetOverflow;
+
+    fn try_from(tree: ITree<u64, V, S>) -> Result<Self, Self::Error> {
+        let nodes: Vec<_> = tree.nodes.into();
+
+        let bases = nodes
+            .chunks(BLOCK_LEN)
+            .map(|block| block[0].start)
+            .collect::<Box<[_]>>();
+
+        let nodes = nodes
+            .into_iter()
+            .enumerate()
+            .map(|(index, node)| {
+                let offset = |value: Option<u64>| {
+                    value
+                        .and_then(|value| u32::try_from(value).ok())
+                        .ok_or(OffsetOverflow(index))
+                };
+
+                Ok(CompressedNode {
+                    start: offset(node.start.checked_sub(bases[index / BLOCK_LEN]))?,
+                    end: offset(node.end.checked_sub(node.start))?,
+                    max: node.max,
+                    value: node.value,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { bases, nodes })
+    }
+}
+
+/// Rejects bases which do not match the blocks of nodes and bounds which overflow when decoded, and [checks][ITree::check] that the decoded nodes are organized as an interval tree
+#[cfg(feature = "serde")]
+impl<'de, V> Deserialize<'de> for CompressedITree<V>
+where
+    V: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let Parts { bases, nodes } = Parts::deserialize(deserializer)?;
+
+        if bases.len() != nodes.len().div_ceil(BLOCK_LEN) {
+            return Err(D::Error::custom("expected one base per block of nodes"));
+        }
+
+        let decoded = nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| {
+                let start = bases[index / BLOCK_LEN]
+                    .checked_add(u64::from(node.start))
+                    .ok_or(OffsetOverflow(index))?;
+                let end = start
+                    .checked_add(u64::from(node.end))
+                    .ok_or(OffsetOverflow(index))?;
+
+                Ok(Node {
+                    start,
+                    end,
+                    max: node.max,
+                    value: (),
+                })
+            })
+            .collect::<Result<Vec<_>, OffsetOverflow>>()
+            .map_err(D::Error::custom)?;
+
+        crate::validate::validate(&decoded).map_err(D::Error::custom)?;
+
+        Ok(Self { bases, nodes })
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+#[serde(rename = "CompressedITree")]
+struct Parts<V> {
+    bases: Box<[u64]>,
+    nodes: Box<[CompressedNode<V>]>,
+}
+
+/// Indicates that the bounds of a node cannot be represented using narrower offsets
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OffsetOverflow(pub usize);
+
+impl fmt::Display for OffsetOverflow {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "node {} does not fit into narrower offsets", self.0)
+    }
+}
+
+impl Error for OffsetOverflow {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use proptest::{collection::vec, test_runner::TestRunner};
+
+    #[test]
+    fn query_random() {
+        const DOM: Range<u64> = 1 << 40..(1 << 40) + 100_000;
+        const LEN: usize = 1000_usize;
+
+        TestRunner::default()
+            .run(
+                &(vec(DOM, LEN), vec(0_u64..1000, LEN), DOM, DOM),
+                |(start, len, query_start, query_end)| {
+                    let tree = ITree::<_, _>::new(
+                        start
+                            .iter()
+                            .zip(&len)
+                            .enumerate()
+                            .map(|(index, (&start, &len))| (start..start + len, index)),
+                    );
+
+                    let mut result1 = Vec::new();
+                    let _ = tree.query(query_start..query_end, |entry| {
+                        result1.push((*entry.start()..*entry.end(), *entry.value()));
+                        ControlFlow::Continue(())
+                    });
+
+                    let tree = CompressedITree::try_from(tree).unwrap();
+
+                    let mut result2 = Vec::new();
+                    let _ = tree.query(query_start..query_end, |range, value| {
+                        result2.push((range, *value));
+                        ControlFlow::Continue(())
+                    });
+
+                    assert_eq!(result1, result2);
+
+                    Ok(())
+                },
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn reject_long_intervals() {
+        let tree = ITree::<_, _>::new([(0..1, ()), (1..1 << 33, ())]);
+
+        assert_eq!(
+            CompressedITree::try_from(tree).unwrap_err(),
+            OffsetOverflow(1)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_rejects_invalid_trees() {
+        let tree =
+            CompressedITree::try_from(ITree::<_, _>::new([(0..5, 'a'), (1..2, 'b')])).unwrap();
+
+        let json = serde_json::to_string(&tree).unwrap();
+        let other = serde_json::from_str::<CompressedITree<char>>(&json).unwrap();
+        assert!(other.iter().eq(tree.iter()));
+
+        let err = serde_json::from_str::<CompressedITree<char>>(
+            &json.replace(r#""bases":[0]"#, r#""bases":[]"#),
+        )
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("expected one base per block of nodes"));
+
+        let err = serde_json::from_str::<CompressedITree<char>>(
+            &json.replace(r#""bases":[0]"#, &format!(r#""bases":[{}]"#, u64::MAX)),
+        )
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("node 0 does not fit into narrower offsets"));
+
+        let err = serde_json::from_str::<CompressedITree<char>>(&json.replacen(
+            r#""max":5"#,
+            r#""max":4"#,
+            1,
+        ))
+        .unwrap_err();
+        assert!(err.to_string().contains("has an invalid maximum end"));
+    }
+}
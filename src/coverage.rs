@@ -0,0 +1,125 @@
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::{ITree, Node};
+
+impl<K, V, S> ITree<K, V, S>
+where
+    K: Ord + Clone,
+    S: AsRef<[Node<K, V>]>,
+{
+    /// Compute the depth profile as disjoint ranges annotated with the number of overlapping intervals
+    ///
+    /// The ranges are ordered, only ranges covered by at least one interval are included and adjacent ranges always differ in depth. Empty and inverted intervals are ignored.
+    pub fn coverage(&self) -> Vec<(Range<K>, usize)> {
+        let nodes = self.nodes.as_ref();
+
+        let mut starts = nodes
+            .iter()
+            .filter(|node| node.start < node.end)
+            .map(|node| &node.start)
+            .peekable();
+
+        let mut ends = nodes
+            .iter()
+            .filter(|node| node.start < node.end)
+            .map(|node| &node.end)
+            .collect::<Vec<_>>();
+        ends.sort_unstable();
+        let mut ends = ends.into_iter().peekable();
+
+        let mut ranges = Vec::<(Range<K>, usize)>::new();
+
+        let mut depth = 0;
+        let mut prev = None::<&K>;
+
+        loop {
+            let pos = match (starts.peek(), ends.peek()) {
+                (Some(start), Some(end)) => *start.min(end),
+                (None, Some(end)) => *end,
+                (Some(_), None) => unreachable!(),
+                (None, None) => break,
+            };
+
+            if let Some(prev) = prev.filter(|_| depth != 0) {
+                match ranges.last_mut() {
+                    Some((range, last_depth)) if range.end == *prev && *last_depth == depth => {
+                        range.end = pos.clone();
+                    }
+                    _ => ranges.push((prev.clone()..pos.clone(), depth)),
+                }
+            }
+
+            while starts.next_if(|start| *start == pos).is_some() {
+                depth += 1;
+            }
+
+            while ends.next_if(|end| *end == pos).is_some() {
+                depth -= 1;
+            }
+
+            prev = Some(pos);
+        }
+
+        ranges
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::ops::ControlFlow;
+
+    use proptest::{collection::vec, test_runner::TestRunner};
+
+    #[test]
+    fn coverage_of_overlapping_intervals() {
+        let tree = ITree::<_, _>::new([
+            (0..10, ()),
+            (5..15, ()),
+            (10..20, ()),
+            (30..40, ()),
+            (35..35, ()),
+        ]);
+
+        assert_eq!(
+            tree.coverage(),
+            [(0..5, 1), (5..15, 2), (15..20, 1), (30..40, 1)]
+        );
+    }
+
+    #[test]
+    fn coverage_matches_point_queries() {
+        TestRunner::default()
+            .run(&vec((0_i32..50, 0_i32..10), 0..50), |items| {
+                let tree =
+                    ITree::<_, _>::new(items.iter().map(|&(start, len)| (start..start + len, ())));
+
+                let coverage = tree.coverage();
+
+                for point in 0..60 {
+                    let mut expected = 0;
+                    let _ = tree.query(point..point + 1, |_| {
+                        expected += 1;
+                        ControlFlow::Continue(())
+                    });
+
+                    let actual = coverage
+                        .iter()
+                        .find(|(range, _)| range.contains(&point))
+                        .map_or(0, |(_, depth)| *depth);
+
+                    assert_eq!(expected, actual);
+                }
+
+                for pair in coverage.windows(2) {
+                    assert!(pair[0].0.end <= pair[1].0.start);
+                    assert!(pair[0].0.end < pair[1].0.start || pair[0].1 != pair[1].1);
+                }
+
+                Ok(())
+            })
+            .unwrap();
+    }
+}
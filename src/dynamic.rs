@@ -0,0 +1,291 @@
+use alloc::vec::Vec;
+use core::iter::once;
+use core::mem::{replace, take};
+use core::ops::{ControlFlow, Range};
+
+use crate::sort::update_max;
+use crate::{Entry, ITree, Item, Node};
+
+/// Combines a large tree with a small overlay of recently inserted items and tombstones of removed items
+///
+/// Queries consult both trees, skipping removed items, and the overlay and tombstones are merged into the base tree once they reach a configurable length.
+#[derive(Debug, Clone)]
+pub struct DynamicITree<K, V> {
+    base: ITree<K, V, Vec<Node<K, V>>>,
+    overlay: ITree<K, V, Vec<Node<K, V>>>,
+    tombstones: Vec<u64>,
+    removed: usize,
+    max_overlay_len: usize,
+}
+
+impl<K, V> DynamicITree<K, V>
+where
+    K: Ord + Clone,
+{
+    /// Wrap the given `base` tree, compacting whenever the overlay and tombstones reach `max_overlay_len` items
+    pub fn new(base: ITree<K, V, Vec<Node<K, V>>>, max_overlay_len: usize) -> Self {
+        Self {
+            base,
+            overlay: ITree::new(None),
+            tombstones: Vec::new(),
+            removed: 0,
+            max_overlay_len,
+        }
+    }
+
+    /// Insert a single item into the overlay
+    ///
+    /// Compacts the overlay into the base tree if it reaches its maximum length.
+    pub fn insert(&mut self, item: Item<K, V>) {
+        self.overlay.extend(once(item));
+
+        self.maybe_compact();
+    }
+
+    /// Remove all items equal to the given `item`
+    ///
+    /// Items in the overlay are removed immediately whereas items in the base tree are recorded as tombstones until the next compaction. Returns whether any items were removed.
+    pub fn remove(&mut self, item: Item<K, V>) -> bool
+    where
+        V: PartialEq,
+    {
+        let overlay_len = self.overlay.len();
+
+        let overlay = replace(&mut self.overlay, ITree::new(None));
+        self.overlay = overlay.retain(|other| *other != item);
+
+        let mut removed = overlay_len != self.overlay.len();
+
+        let nodes = &self.base.nodes;
+        let start = nodes.partition_point(|node| node.start < item.0.start);
+
+        for (index, node) in nodes[start..]
+            .iter()
+            .enumerate()
+            .take_while(|(_, node)| node.start == item.0.start)
+        {
+            let index = start + index;
+
+            if *node == item && !is_removed(&self.tombstones, index) {
+                if self.tombstones.is_empty() {
+                    self.tombstones.resize(nodes.len().div_ceil(64), 0);
+                }
+
+                self.tombstones[index / 64] |= 1 << (index % 64);
+                self.removed += 1;
+
+                removed = true;
+            }
+        }
+
+        self.maybe_compact();
+
+        removed
+    }
+
+    fn maybe_compact(&mut self) {
+        if self.overlay.len() + self.removed >= self.max_overlay_len {
+            self.compact();
+        }
+    }
+
+    /// Remove the tombstoned items from the base tree and merge the overlay into it
+    pub fn compact(&mut self) {
+        if self.removed != 0 {
+            let nodes = &mut self.base.nodes;
+
+            let mut index = 0;
+            nodes.retain(|_| {
+                let keep = !is_removed(&self.tombstones, index);
+                index += 1;
+                keep
+            });
+
+            for node in &mut *nodes {
+                node.max = node.end.clone();
+            }
+
+            if !nodes.is_empty() {
+                update_max(nodes);
+            }
+
+            self.tombstones.clear();
+            self.removed = 0;
+        }
+
+        let overlay = take(&mut self.overlay.nodes);
+
+        if !overlay.is_empty() {
+            self.base.extend(overlay.into_iter().map(Node::into_item));
+        }
+    }
+
+    /// Query for all intervals overlapping the given interval in both the base tree and the overlay
+    pub fn query<'a, H>(&'a self, interval: Range<K>, mut handler: H) -> ControlFlow<()>
+    where
+        H: FnMut(Entry<'a, K, V>) -> ControlFlow<()>,
+    {
+        let nodes = &*self.base.nodes;
+
+        if self.removed == 0 {
+            self.base.query(interval.clone(), &mut handler)?;
+        } else if !nodes.is_empty() {
+            query_base(&interval, nodes, 0, &self.tombstones, &mut handler)?;
+        }
+
+        self.overlay.query(interval, handler)
+    }
+
+    /// Iterate over all intervals in the base tree and the overlay, skipping removed items
+    pub fn iter(&self) -> impl Iterator<Item = &Node<K, V>> {
+        self.base
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !is_removed(&self.tombstones, *index))
+            .map(|(_, node)| node)
+            .chain(self.overlay.iter())
+    }
+
+    /// The number of items in the base tree and the overlay, not counting removed items
+    pub fn len(&self) -> usize {
+        self.base.len() - self.removed + self.overlay.len()
+    }
+
+    /// Whether neither the base tree nor the overlay contain any items which were not removed
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The base tree which contains all items inserted before the last compaction, including removed ones
+    pub fn base(&self) -> &ITree<K, V, Vec<Node<K, V>>> {
+        &self.base
+    }
+
+    /// Compact the overlay and tombstones and return the resulting tree
+    pub fn into_tree(mut self) -> ITree<K, V, Vec<Node<K, V>>> {
+        self.compact();
+
+        self.base
+    }
+}
+
+fn is_removed(tombstones: &[u64], index: usize) -> bool {
+    tombstones
+        .get(index / 64)
+        .is_some_and(|word| word & (1 << (index % 64)) != 0)
+}
+
+fn query_base<'a, K, V, H>(
+    interval: &Range<K>,
+    nodes: &'a [Node<K, V>],
+    offset: usize,
+    tombstones: &[u64],
+    handler: &mut H,
+) -> ControlFlow<()>
+where
+    K: Ord,
+    H: FnMut(Entry<'a, K, V>) -> ControlFlow<()>,
+{
+    let (left, [mid, right @ ..]) = nodes.split_at(nodes.len() / 2) else {
+        unreachable!()
+    };
+
+    if interval.start >= mid.max {
+        return ControlFlow::Continue(());
+    }
+
+    if !left.is_empty() {
+        query_base(interval, left, offset, tombstones, handler)?;
+    }
+
+    if interval.end > mid.start {
+        let index = offset + left.len();
+
+        if interval.start < mid.end && !is_removed(tombstones, index) {
+            handler(mid.into())?;
+        }
+
+        if !right.is_empty() {
+            query_base(interval, right, index + 1, tombstones, handler)?;
+        }
+    }
+
+    ControlFlow::Continue(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_and_compact() {
+        let mut tree = DynamicITree::new(ITree::new([(0..10, 0), (20..30, 1)]), 2);
+
+        tree.insert((5..15, 2));
+        assert_eq!(tree.base().len(), 2);
+
+        let mut result = Vec::new();
+        let _ = tree.query(8..12, |entry| {
+            result.push(*entry.value());
+            ControlFlow::Continue(())
+        });
+        result.sort_unstable();
+        assert_eq!(result, [0, 2]);
+
+        tree.insert((25..35, 3));
+        assert_eq!(tree.base().len(), 4);
+        assert!(tree.base().is_valid());
+
+        let tree = tree.into_tree();
+        assert!(tree
+            .iter()
+            .eq(&[(0..10, 0), (5..15, 2), (20..30, 1), (25..35, 3)]));
+    }
+
+    #[test]
+    fn remove_and_compact() {
+        let mut tree = DynamicITree::new(ITree::new([(0..10, 0), (0..10, 0), (20..30, 1)]), 4);
+
+        tree.insert((5..15, 2));
+
+        assert!(tree.remove((5..15, 2)));
+        assert!(tree.remove((0..10, 0)));
+        assert!(!tree.remove((0..10, 1)));
+        assert_eq!(tree.len(), 1);
+        assert_eq!(tree.base().len(), 3);
+
+        let mut result = Vec::new();
+        let _ = tree.query(0..40, |entry| {
+            result.push(*entry.value());
+            ControlFlow::Continue(())
+        });
+        assert_eq!(result, [1]);
+
+        tree.insert((40..50, 3));
+        tree.insert((0..10, 4));
+        assert_eq!(tree.base().len(), 3);
+        assert!(tree.base().is_valid());
+        assert!(tree
+            .base()
+            .iter()
+            .eq(&[(0..10, 4), (20..30, 1), (40..50, 3)]));
+    }
+
+    #[test]
+    fn values_need_not_be_comparable() {
+        #[derive(Debug, Clone)]
+        struct Opaque;
+
+        let mut tree = DynamicITree::new(ITree::new([(0..10, Opaque)]), 2);
+
+        tree.insert((5..15, Opaque));
+        tree.compact();
+
+        let mut count = 0;
+        let _ = tree.query(0..20, |_| {
+            count += 1;
+            ControlFlow::Continue(())
+        });
+        assert_eq!(count, 2);
+    }
+}
@@ -0,0 +1,123 @@
+use core::cmp::Ordering;
+use core::fmt;
+
+#[cfg(feature = "bytemuck")]
+use bytemuck::{AnyBitPattern, NoUninit};
+
+use crate::Codec;
+
+/// Integer stored in little-endian byte order independently of the target
+///
+/// Using it as key or value type makes the in-memory representation of the nodes match the little-endian binary format on big-endian targets, so that trees can be loaded without copying via `ITree::from_bytes`. On little-endian targets, it has no overhead.
+#[derive(Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "bytemuck", derive(AnyBitPattern, NoUninit))]
+#[repr(transparent)]
+pub struct LittleEndian<T>(T);
+
+impl<T> LittleEndian<T>
+where
+    T: sealed::ByteOrder,
+{
+    /// Store the given `value` in little-endian byte order
+    pub fn new(value: T) -> Self {
+        Self(value.swap_le())
+    }
+
+    /// Load the value in native byte order
+    pub fn get(self) -> T {
+        self.0.swap_le()
+    }
+}
+
+impl<T> From<T> for LittleEndian<T>
+where
+    T: sealed::ByteOrder,
+{
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T> PartialOrd for LittleEndian<T>
+where
+    T: sealed::ByteOrder + Ord,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for LittleEndian<T>
+where
+    T: sealed::ByteOrder + Ord,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.get().cmp(&other.get())
+    }
+}
+
+impl<T> fmt::Debug for LittleEndian<T>
+where
+    T: sealed::ByteOrder + fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.get().fmt(fmt)
+    }
+}
+
+impl<T> Codec for LittleEndian<T>
+where
+    T: sealed::ByteOrder + Codec,
+{
+    const SIZE: usize = T::SIZE;
+
+    const TAG: u8 = T::TAG;
+
+    fn encode(&self, buf: &mut [u8]) {
+        self.get().encode(buf);
+    }
+
+    fn decode(buf: &[u8]) -> Self {
+        Self::new(T::decode(buf))
+    }
+}
+
+pub(crate) mod sealed {
+    pub trait ByteOrder: Copy {
+        fn swap_le(self) -> Self;
+    }
+}
+
+macro_rules! impl_byte_order {
+    ($($type:ty),*) => {
+        $(
+            impl sealed::ByteOrder for $type {
+                fn swap_le(self) -> Self {
+                    self.to_le()
+                }
+            }
+        )*
+    };
+}
+
+impl_byte_order!(u16, u32, u64, u128, i16, i32, i64, i128);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ordered_by_value() {
+        let lhs = LittleEndian::new(0x0100_u16);
+        let rhs = LittleEndian::new(0x00ff_u16);
+
+        assert!(lhs > rhs);
+        assert_eq!(lhs.get(), 0x0100);
+        assert_eq!(format!("{lhs:?}"), "256");
+
+        let mut buf = [0; 2];
+        lhs.encode(&mut buf);
+        assert_eq!(buf, [0x00, 0x01]);
+        assert_eq!(buf, lhs.0.to_ne_bytes());
+    }
+}
@@ -0,0 +1,105 @@
+use alloc::boxed::Box;
+#[cfg(not(feature = "std"))]
+use core::cell::OnceCell as Lazy;
+use core::ops::Deref;
+#[cfg(feature = "std")]
+use std::sync::OnceLock as Lazy;
+
+use crate::{ITree, Node};
+
+/// Interval tree which can additionally be iterated in the order of the interval upper bounds
+///
+/// The permutation required for this is built on first use by [`iter_by_end`][Self::iter_by_end] and then kept alongside the tree. All other methods of [`ITree`] are available via [`Deref`].
+#[derive(Debug, Default, Clone)]
+pub struct EndOrderedITree<K, V, S = Box<[Node<K, V>]>> {
+    tree: ITree<K, V, S>,
+    order: Lazy<Box<[usize]>>,
+}
+
+impl<K, V, S> EndOrderedITree<K, V, S>
+where
+    S: AsRef<[Node<K, V>]>,
+{
+    /// Wrap the given `tree` without building the permutation yet
+    pub fn new(tree: ITree<K, V, S>) -> Self {
+        Self {
+            tree,
+            order: Lazy::new(),
+        }
+    }
+
+    /// Unwrap the tree, discarding the permutation
+    pub fn into_inner(self) -> ITree<K, V, S> {
+        self.tree
+    }
+
+    /// Iterate over all nodes in the order of their interval upper bounds
+    ///
+    /// Nodes with equal upper bounds are yielded in the order of their interval starts.
+    pub fn iter_by_end(&self) -> impl DoubleEndedIterator<Item = &Node<K, V>> + ExactSizeIterator
+    where
+        K: Ord,
+    {
+        let nodes = self.tree.nodes.as_ref();
+
+        let order = self.order.get_or_init(|| {
+            let mut order = (0..nodes.len()).collect::<Box<[_]>>();
+            order.sort_by_key(|&index| &nodes[index].end);
+            order
+        });
+
+        order.iter().map(|&index| &nodes[index])
+    }
+}
+
+impl<K, V, S> Deref for EndOrderedITree<K, V, S> {
+    type Target = ITree<K, V, S>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.tree
+    }
+}
+
+impl<K, V, S> From<ITree<K, V, S>> for EndOrderedITree<K, V, S>
+where
+    S: AsRef<[Node<K, V>]>,
+{
+    fn from(tree: ITree<K, V, S>) -> Self {
+        Self::new(tree)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::ops::Range;
+
+    use proptest::{collection::vec, test_runner::TestRunner};
+
+    #[test]
+    fn iter_by_end_random() {
+        const DOM: Range<i32> = -1000..1000;
+        const LEN: usize = 1000_usize;
+
+        TestRunner::default()
+            .run(&(vec(DOM, LEN), vec(DOM, LEN)), |(start, end)| {
+                let tree = EndOrderedITree::new(ITree::<_, _>::new(
+                    start
+                        .iter()
+                        .zip(&end)
+                        .enumerate()
+                        .map(|(index, (&start, &end))| (start..end, index)),
+                ));
+
+                let mut nodes = tree.iter().collect::<Vec<_>>();
+                nodes.sort_by_key(|node| node.end);
+
+                assert!(tree.iter_by_end().eq(nodes.iter().copied()));
+                assert!(tree.iter_by_end().rev().eq(nodes.iter().rev().copied()));
+
+                Ok(())
+            })
+            .unwrap()
+    }
+}
@@ -0,0 +1,256 @@
+use std::collections::BinaryHeap;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::marker::PhantomData;
+
+use crate::codec::{decode_item, encode_item, item_size};
+use crate::merge::Head;
+use crate::sort::{cmp_intervals, node, update_max};
+use crate::{Codec, ITree, ITreeWriter, Item, Node};
+
+/// Constructs trees from more items than fit into memory by spilling sorted runs to temporary files
+///
+/// Requires the `external` feature. Items are buffered until `run_len` of them have been collected, then sorted and written to an anonymous temporary file using their [`Codec`] representation. Building the tree merges these runs so that the unsorted items never need to be held in memory at once.
+#[derive(Debug)]
+pub struct ExternalITreeBuilder<K, V> {
+    run_len: usize,
+    items: Vec<Item<K, V>>,
+    runs: Vec<(File, usize)>,
+}
+
+impl<K, V> ExternalITreeBuilder<K, V>
+where
+    K: Ord + Clone + Codec,
+    V: Codec,
+{
+    /// Create an empty builder which spills runs of `run_len` items
+    ///
+    /// # Panics
+    ///
+    /// If `run_len` is zero.
+    pub fn new(run_len: usize) -> Self {
+        assert_ne!(run_len, 0, "run length must not be zero");
+
+        Self {
+            run_len,
+            items: Vec::new(),
+            runs: Vec::new(),
+        }
+    }
+
+    /// Add a single item, spilling a sorted run if the buffer is full
+    pub fn push(&mut self, item: Item<K, V>) -> io::Result<()> {
+        if self.items.capacity() == 0 {
+            self.items.reserve_exact(self.run_len);
+        }
+
+        self.items.push(item);
+
+        if self.items.len() >= self.run_len {
+            self.spill()?;
+        }
+
+        Ok(())
+    }
+
+    /// Add all the given `items`, spilling sorted runs as necessary
+    pub fn extend<I>(&mut self, items: I) -> io::Result<()>
+    where
+        I: IntoIterator<Item = Item<K, V>>,
+    {
+        for item in items {
+            self.push(item)?;
+        }
+
+        Ok(())
+    }
+
+    /// The number of items collected so far
+    pub fn len(&self) -> usize {
+        self.items.len() + self.runs.iter().map(|(_file, len)| len).sum::<usize>()
+    }
+
+    /// Whether no items were collected so far
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn spill(&mut self) -> io::Result<()> {
+        self.items
+            .sort_unstable_by(|lhs, rhs| cmp_intervals(&lhs.0, &rhs.0));
+
+        let mut writer = BufWriter::new(tempfile::tempfile()?);
+        let mut buf = vec![0; item_size::<K, V>()];
+
+        for item in &self.items {
+            encode_item(item, &mut buf);
+            writer.write_all(&buf)?;
+        }
+
+        let mut file = writer.into_inner().map_err(|err| err.into_error())?;
+        file.seek(SeekFrom::Start(0))?;
+
+        self.runs.push((file, self.items.len()));
+        self.items.clear();
+
+        Ok(())
+    }
+
+    /// Construct a new tree in memory by merging the spilled runs
+    pub fn build<S>(mut self) -> io::Result<ITree<K, V, S>>
+    where
+        S: From<Vec<Node<K, V>>>,
+    {
+        let mut nodes = Vec::with_capacity(self.len());
+
+        self.merge(|item| {
+            nodes.push(node(item));
+            Ok(())
+        })?;
+
+        if !nodes.is_empty() {
+            update_max(&mut nodes);
+        }
+
+        Ok(ITree {
+            nodes: nodes.into(),
+            _marker: PhantomData,
+        })
+    }
+
+    /// Write the tree using the versioned binary format by merging the spilled runs
+    ///
+    /// Uses an [`ITreeWriter`] so that the tree never needs to be held in memory.
+    pub fn write_to<W>(mut self, writer: W) -> io::Result<W>
+    where
+        W: Read + Write + Seek,
+    {
+        let mut writer = ITreeWriter::new(writer, self.len())?;
+
+        self.merge(|item| writer.push(item))?;
+
+        writer.finish()
+    }
+
+    fn merge<F>(&mut self, mut f: F) -> io::Result<()>
+    where
+        F: FnMut(Item<K, V>) -> io::Result<()>,
+    {
+        if !self.items.is_empty() {
+            self.spill()?;
+        }
+
+        let mut runs = self
+            .runs
+            .drain(..)
+            .map(|(file, len)| Run::new(file, len))
+            .collect::<Vec<_>>();
+
+        let mut heads = BinaryHeap::with_capacity(runs.len());
+
+        for (index, run) in runs.iter_mut().enumerate() {
+            if let Some(item) = run.next()? {
+                heads.push(Head::new(item, index));
+            }
+        }
+
+        while let Some(head) = heads.pop() {
+            if let Some(item) = runs[head.run].next()? {
+                heads.push(Head::new(item, head.run));
+            }
+
+            f(head.item)?;
+        }
+
+        Ok(())
+    }
+}
+
+struct Run<K, V> {
+    reader: BufReader<File>,
+    len: usize,
+    buf: Vec<u8>,
+    _marker: PhantomData<(K, V)>,
+}
+
+impl<K, V> Run<K, V>
+where
+    K: Codec,
+    V: Codec,
+{
+    fn new(file: File, len: usize) -> Self {
+        Self {
+            reader: BufReader::new(file),
+            len,
+            buf: vec![0; item_size::<K, V>()],
+            _marker: PhantomData,
+        }
+    }
+
+    fn next(&mut self) -> io::Result<Option<Item<K, V>>> {
+        if self.len == 0 {
+            return Ok(None);
+        }
+
+        self.len -= 1;
+
+        self.reader.read_exact(&mut self.buf)?;
+
+        Ok(Some(decode_item(&self.buf)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::Cursor;
+
+    use proptest::{collection::vec, test_runner::TestRunner};
+
+    #[test]
+    fn build_from_spilled_runs() {
+        TestRunner::default()
+            .run(
+                &(
+                    vec(-1000_i32..1000, 100),
+                    vec(-1000_i32..1000, 100),
+                    1_usize..20,
+                ),
+                |(start, end, run_len)| {
+                    let items = start
+                        .iter()
+                        .zip(&end)
+                        .enumerate()
+                        .map(|(index, (&start, &end))| (start..end, index as u32))
+                        .collect::<Vec<_>>();
+
+                    let mut builder = ExternalITreeBuilder::new(run_len);
+                    builder.extend(items.iter().cloned()).unwrap();
+                    assert_eq!(builder.len(), items.len());
+
+                    let tree1: ITree<_, _> = builder.build().unwrap();
+
+                    let mut builder = ExternalITreeBuilder::new(run_len);
+                    builder.extend(items.iter().cloned()).unwrap();
+
+                    let buf = builder
+                        .write_to(Cursor::new(Vec::new()))
+                        .unwrap()
+                        .into_inner();
+                    let read = ITree::<i32, u32>::read_from(&*buf).unwrap();
+                    assert_eq!(read.as_inner(), tree1.as_inner());
+                    assert!(tree1.is_valid());
+
+                    let tree2 = ITree::<_, _>::new(items);
+                    assert!(tree1
+                        .iter()
+                        .map(Node::interval)
+                        .eq(tree2.iter().map(Node::interval)));
+
+                    Ok(())
+                },
+            )
+            .unwrap()
+    }
+}
@@ -0,0 +1,320 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use core::ops::{ControlFlow, Range};
+
+#[cfg(feature = "serde")]
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize};
+
+use crate::{Entry, ITree, InvalidTree, Item, Node};
+
+/// Interval tree storing its nodes in breadth-first (Eytzinger) order
+///
+/// The children of the node at index `i` are found at `2 * i + 1` and `2 * i + 2`, so the first levels of every descent share a few cache lines.
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct EytzingerITree<K, V, S = Box<[Node<K, V>]>> {
+    nodes: S,
+    _marker: PhantomData<(K, V)>,
+}
+
+impl<K, V, S> EytzingerITree<K, V, S>
+where
+    K: Ord + Clone,
+    S: From<Vec<Node<K, V>>>,
+{
+    /// Construct a new tree by sorting the given `items`
+    pub fn new<I>(items: I) -> Self
+    where
+        I: IntoIterator<Item = Item<K, V>>,
+    {
+        ITree::<K, V, Vec<Node<K, V>>>::new(items).into()
+    }
+}
+
+impl<K, V, S> EytzingerITree<K, V, S>
+where
+    S: AsRef<[Node<K, V>]>,
+{
+    /// The number of items in the tree
+    pub fn len(&self) -> usize {
+        self.nodes.as_ref().len()
+    }
+
+    /// Whether the tree contains no items
+    pub fn is_empty(&self) -> bool {
+        self.nodes.as_ref().is_empty()
+    }
+
+    /// Iterate over all nodes in breadth-first order
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &Node<K, V>> {
+        self.nodes.as_ref().iter()
+    }
+
+    /// Query for all intervals overlapping the given interval
+    pub fn query<'a, H>(&'a self, interval: Range<K>, mut handler: H) -> ControlFlow<()>
+    where
+        K: Ord,
+        H: FnMut(Entry<'a, K, V>) -> ControlFlow<()>,
+    {
+        query(&interval, &mut handler, self.nodes.as_ref(), 0)
+    }
+}
+
+impl<K, V, S> EytzingerITree<K, V, S>
+where
+    K: Ord,
+    S: AsRef<[Node<K, V>]>,
+{
+    /// Interprets the given `nodes` as a tree in breadth-first order after verifying that they are organized as an interval tree
+    ///
+    /// Reported indices refer to the breadth-first order and a node is unsorted if it starts before its in-order predecessor.
+    pub fn from_nodes(nodes: S) -> Result<Self, InvalidTree> {
+        validate(nodes.as_ref())?;
+
+        Ok(Self {
+            nodes,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Verify that the nodes are organized as an interval tree, reporting the first violation
+    pub fn check(&self) -> Result<(), InvalidTree> {
+        validate(self.nodes.as_ref())
+    }
+}
+
+/// [Checks][EytzingerITree::from_nodes] that the nodes are organized as an interval tree
+#[cfg(feature = "serde")]
+impl<'de, K, V, S> Deserialize<'de> for EytzingerITree<K, V, S>
+where
+    K: Ord,
+    S: AsRef<[Node<K, V>]> + Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let nodes = S::deserialize(deserializer)?;
+
+        Self::from_nodes(nodes).map_err(D::Error::custom)
+    }
+}
+
+/// Re-arranges the nodes of the given `tree` into breadth-first order
+impl<K, V, S, T> From<ITree<K, V, T>> for EytzingerITree<K, V, S>
+where
+    K: Ord + Clone,
+    S: From<Vec<Node<K, V>>>,
+    T: Into<Vec<Node<K, V>>>,
+{
+    fn from(tree: ITree<K, V, T>) -> Self {
+        let sorted: Vec<_> = tree.nodes.into();
+
+        let mut nodes = Vec::with_capacity(sorted.len());
+        nodes.resize_with(sorted.len(), || None);
+
+        fill(&mut sorted.into_iter(), &mut nodes, 0);
+
+        let mut nodes = nodes
+            .into_iter()
+            .map(|node| node.unwrap())
+            .collect::<Vec<_>>();
+
+        for index in (0..nodes.len()).rev() {
+            let mut max = nodes[index].end.clone();
+
+            for child in [2 * index + 1, 2 * index + 2] {
+                if let Some(child) = nodes.get(child) {
+                    if max < child.max {
+                        max = child.max.clone();
+                    }
+                }
+            }
+
+            nodes[index].max = max;
+        }
+
+        Self {
+            nodes: nodes.into(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+fn fill<K, V, I>(sorted: &mut I, nodes: &mut [Option<Node<K, V>>], index: usize)
+where
+    I: Iterator<Item = Node<K, V>>,
+{
+    if index < nodes.len() {
+        fill(sorted, nodes, 2 * index + 1);
+        nodes[index] = sorted.next();
+        fill(sorted, nodes, 2 * index + 2);
+    }
+}
+
+fn validate<K, V>(nodes: &[Node<K, V>]) -> Result<(), InvalidTree>
+where
+    K: Ord,
+{
+    validate_order(nodes, 0, &mut None)?;
+
+    for (index, node) in nodes.iter().enumerate() {
+        let mut max = &node.end;
+
+        for child in [2 * index + 1, 2 * index + 2] {
+            if let Some(child) = nodes.get(child) {
+                max = max.max(&child.max);
+            }
+        }
+
+        if node.max != *max {
+            return Err(InvalidTree::InvalidMax(index));
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_order<'a, K, V>(
+    nodes: &'a [Node<K, V>],
+    index: usize,
+    prev: &mut Option<&'a K>,
+) -> Result<(), InvalidTree>
+where
+    K: Ord,
+{
+    if let Some(node) = nodes.get(index) {
+        validate_order(nodes, 2 * index + 1, prev)?;
+
+        if prev.is_some_and(|prev| *prev > node.start) {
+            return Err(InvalidTree::Unsorted(index));
+        }
+
+        *prev = Some(&node.start);
+
+        validate_order(nodes, 2 * index + 2, prev)?;
+    }
+
+    Ok(())
+}
+
+fn query<'a, K, V, H>(
+    interval: &Range<K>,
+    handler: &mut H,
+    nodes: &'a [Node<K, V>],
+    mut index: usize,
+) -> ControlFlow<()>
+where
+    K: Ord,
+    H: FnMut(Entry<'a, K, V>) -> ControlFlow<()>,
+{
+    while let Some(node) = nodes.get(index) {
+        if interval.start >= node.max {
+            break;
+        }
+
+        query(interval, handler, nodes, 2 * index + 1)?;
+
+        if interval.end <= node.start {
+            break;
+        }
+
+        if interval.start < node.end {
+            handler(node.into())?;
+        }
+
+        index = 2 * index + 2;
+    }
+
+    ControlFlow::Continue(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use proptest::{collection::vec, test_runner::TestRunner};
+
+    #[test]
+    fn query_random() {
+        const DOM: Range<i32> = -1000..1000;
+        const LEN: usize = 1000_usize;
+
+        TestRunner::default()
+            .run(
+                &(vec(DOM, LEN), vec(DOM, LEN), DOM, DOM),
+                |(start, end, query_start, query_end)| {
+                    let tree = ITree::<_, _>::new(
+                        start
+                            .iter()
+                            .zip(&end)
+                            .enumerate()
+                            .map(|(index, (&start, &end))| (start..end, index)),
+                    );
+
+                    let mut result1 = Vec::new();
+                    let _ = tree.query(query_start..query_end, |entry| {
+                        result1.push(*entry.value());
+                        ControlFlow::Continue(())
+                    });
+
+                    let tree = EytzingerITree::<_, _>::from(tree);
+                    assert_eq!(tree.check(), Ok(()));
+
+                    let mut result2 = Vec::new();
+                    let _ = tree.query(query_start..query_end, |entry| {
+                        result2.push(*entry.value());
+                        ControlFlow::Continue(())
+                    });
+
+                    result1.sort_unstable();
+                    result2.sort_unstable();
+                    assert_eq!(result1, result2);
+
+                    Ok(())
+                },
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn from_nodes_rejects_invalid_trees() {
+        let tree = EytzingerITree::<_, _>::new([(0..5, 'a'), (1..2, 'b'), (2..3, 'c')]);
+
+        let mut nodes = tree.iter().cloned().collect::<Vec<_>>();
+        nodes[0].max = 4;
+        assert_eq!(
+            EytzingerITree::<_, _, Vec<_>>::from_nodes(nodes).unwrap_err(),
+            InvalidTree::InvalidMax(0)
+        );
+
+        let mut nodes = tree.iter().cloned().collect::<Vec<_>>();
+        nodes.swap(1, 2);
+        assert_eq!(
+            EytzingerITree::<_, _, Vec<_>>::from_nodes(nodes).unwrap_err(),
+            InvalidTree::Unsorted(0)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_rejects_invalid_trees() {
+        let tree = EytzingerITree::<i32, char>::new([(0..5, 'a'), (1..2, 'b'), (2..3, 'c')]);
+
+        let json = serde_json::to_string(&tree).unwrap();
+        let other = serde_json::from_str::<EytzingerITree<i32, char>>(&json).unwrap();
+        assert!(other.iter().eq(tree.iter()));
+
+        let err = serde_json::from_str::<EytzingerITree<i32, char>>(&json.replacen(
+            r#""max":5"#,
+            r#""max":4"#,
+            1,
+        ))
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("node 0 has an invalid maximum end"));
+    }
+}
@@ -0,0 +1,295 @@
+//! A C API for trees mapping `uint64_t` intervals to `uint64_t` values
+//!
+//! Requires the `ffi` feature. The declarations are provided by `include/sif_itree.h` and the functions are exported when this crate is built as a `staticlib` or `cdylib`. Values are typically indices into storage owned by the caller.
+
+use std::ffi::c_void;
+#[cfg(all(feature = "mmap", target_endian = "little", unix))]
+use std::ffi::OsStr;
+#[cfg(all(feature = "mmap", target_endian = "little"))]
+use std::ffi::{c_char, CStr};
+use std::marker::PhantomData;
+use std::ops::ControlFlow;
+#[cfg(all(feature = "mmap", target_endian = "little", unix))]
+use std::os::unix::ffi::OsStrExt;
+#[cfg(all(feature = "mmap", target_endian = "little"))]
+use std::path::Path;
+#[cfg(all(feature = "mmap", target_endian = "little"))]
+use std::ptr::null_mut;
+use std::ptr::slice_from_raw_parts_mut;
+use std::slice::from_raw_parts;
+
+#[cfg(all(feature = "mmap", target_endian = "little"))]
+use crate::MmapNodes;
+use crate::{Entry, ITree, Node};
+
+/// Opaque handle to a tree owned by C code
+#[derive(Debug)]
+pub struct SifITree(Storage);
+
+#[derive(Debug)]
+enum Storage {
+    Owned(ITree<u64, u64>),
+    #[cfg(all(feature = "mmap", target_endian = "little"))]
+    Mapped(ITree<u64, u64, MmapNodes<u64, u64>>),
+}
+
+/// Callback invoked for each item overlapping a query, returning `false` to stop the query
+pub type SifITreeCallback =
+    extern "C" fn(start: u64, end: u64, value: u64, context: *mut c_void) -> bool;
+
+/// Construct a new tree from `len` interval starts, ends and values
+///
+/// The tree must be released using [`sif_itree_free`].
+///
+/// # Safety
+///
+/// Unless `len` is zero, `starts`, `ends` and `values` must each point to `len` readable elements.
+#[allow(unsafe_code)]
+#[no_mangle]
+pub unsafe extern "C" fn sif_itree_new(
+    starts: *const u64,
+    ends: *const u64,
+    values: *const u64,
+    len: usize,
+) -> *mut SifITree {
+    let (starts, ends, values) = if len != 0 {
+        // SAFETY: The caller guarantees that the pointers refer to `len` elements.
+        unsafe {
+            (
+                from_raw_parts(starts, len),
+                from_raw_parts(ends, len),
+                from_raw_parts(values, len),
+            )
+        }
+    } else {
+        (&[][..], &[][..], &[][..])
+    };
+
+    let tree = ITree::from_columns(starts, ends, values.to_vec());
+
+    Box::into_raw(Box::new(SifITree(Storage::Owned(tree))))
+}
+
+/// Memory map a file written using [`write_to`][ITree::write_to] as a tree, returning null if `path` is null or the file cannot be opened or is invalid
+///
+/// Requires the `mmap` feature and a little-endian target. On Unix, `path` can contain arbitrary bytes whereas it must be valid UTF-8 on other platforms. The tree must be released using [`sif_itree_free`].
+///
+/// # Safety
+///
+/// `path` must be null or point to a NUL-terminated string and the file must not be modified while the tree exists.
+#[cfg(all(feature = "mmap", target_endian = "little"))]
+#[allow(unsafe_code)]
+#[no_mangle]
+pub unsafe extern "C" fn sif_itree_from_mmap(path: *const c_char) -> *mut SifITree {
+    if path.is_null() {
+        return null_mut();
+    }
+
+    // SAFETY: The caller guarantees that `path` is NUL-terminated.
+    let path = unsafe { CStr::from_ptr(path) };
+
+    #[cfg(unix)]
+    let path = Path::new(OsStr::from_bytes(path.to_bytes()));
+
+    #[cfg(not(unix))]
+    let Ok(path) = path.to_str().map(Path::new) else {
+        return null_mut();
+    };
+
+    // SAFETY: The caller guarantees that the file is not modified.
+    match unsafe { ITree::open_mmap(path) } {
+        Ok(tree) => Box::into_raw(Box::new(SifITree(Storage::Mapped(tree)))),
+        Err(_err) => null_mut(),
+    }
+}
+
+/// Release a tree, doing nothing if `tree` is null
+///
+/// # Safety
+///
+/// `tree` must be null or have been returned by this API and not yet been released.
+#[allow(unsafe_code)]
+#[no_mangle]
+pub unsafe extern "C" fn sif_itree_free(tree: *mut SifITree) {
+    if !tree.is_null() {
+        // SAFETY: The caller guarantees that `tree` was allocated by this API and is still live.
+        drop(unsafe { Box::from_raw(tree) });
+    }
+}
+
+/// The number of items in the tree
+///
+/// # Safety
+///
+/// `tree` must not be null and must have been returned by this API and not yet been released.
+#[allow(unsafe_code)]
+#[no_mangle]
+pub unsafe extern "C" fn sif_itree_len(tree: *const SifITree) -> usize {
+    // SAFETY: The caller guarantees that `tree` is live.
+    match &unsafe { &*tree }.0 {
+        Storage::Owned(tree) => tree.len(),
+        #[cfg(all(feature = "mmap", target_endian = "little"))]
+        Storage::Mapped(tree) => tree.len(),
+    }
+}
+
+/// Query for all intervals overlapping `start..end`, invoking `callback` with `context` for each, and return whether the query ran to completion
+///
+/// Returns `false` without querying the tree if `callback` is null.
+///
+/// # Safety
+///
+/// `tree` must not be null and must have been returned by this API and not yet been released.
+#[allow(unsafe_code)]
+#[no_mangle]
+pub unsafe extern "C" fn sif_itree_query(
+    tree: *const SifITree,
+    start: u64,
+    end: u64,
+    callback: Option<SifITreeCallback>,
+    context: *mut c_void,
+) -> bool {
+    let Some(callback) = callback else {
+        return false;
+    };
+
+    let handler = |entry: Entry<'_, u64, u64>| {
+        if callback(*entry.start(), *entry.end(), *entry.value(), context) {
+            ControlFlow::Continue(())
+        } else {
+            ControlFlow::Break(())
+        }
+    };
+
+    // SAFETY: The caller guarantees that `tree` is live.
+    let res = match &unsafe { &*tree }.0 {
+        Storage::Owned(tree) => tree.query(start..end, handler),
+        #[cfg(all(feature = "mmap", target_endian = "little"))]
+        Storage::Mapped(tree) => tree.query(start..end, handler),
+    };
+
+    res.is_continue()
+}
+
+impl<K, V> ITree<K, V> {
+    /// Decompose the tree into a pointer to its nodes and their number, e.g. to pass ownership across an FFI boundary
+    ///
+    /// Requires the `ffi` feature. The tree can be recovered using [`from_raw_parts`][Self::from_raw_parts].
+    pub fn into_raw_parts(self) -> (*mut Node<K, V>, usize) {
+        let len = self.nodes.len();
+
+        (Box::into_raw(self.nodes).cast(), len)
+    }
+
+    /// Recover a tree decomposed using [`into_raw_parts`][Self::into_raw_parts]
+    ///
+    /// Requires the `ffi` feature.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` and `len` must have been returned by [`into_raw_parts`][Self::into_raw_parts] for the same `K` and `V` and the tree must not have been recovered already.
+    #[allow(unsafe_code)]
+    pub unsafe fn from_raw_parts(ptr: *mut Node<K, V>, len: usize) -> Self {
+        // SAFETY: The caller guarantees that this is the boxed slice decomposed by `into_raw_parts`.
+        let nodes = unsafe { Box::from_raw(slice_from_raw_parts_mut(ptr, len)) };
+
+        Self {
+            nodes,
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    extern "C" fn collect(_start: u64, _end: u64, value: u64, context: *mut c_void) -> bool {
+        #[allow(unsafe_code)]
+        // SAFETY: The tests pass a pointer to a live vector.
+        let values = unsafe { &mut *context.cast::<Vec<u64>>() };
+
+        values.push(value);
+
+        values.len() < 2
+    }
+
+    #[test]
+    #[allow(unsafe_code)]
+    fn query_via_c_api() {
+        let starts = [0, 1, 2];
+        let ends = [5, 2, 3];
+        let values = [10, 11, 12];
+
+        // SAFETY: All arrays have three elements and the tree is released exactly once.
+        unsafe {
+            let tree = sif_itree_new(starts.as_ptr(), ends.as_ptr(), values.as_ptr(), 3);
+            assert_eq!(sif_itree_len(tree), 3);
+
+            let mut result = Vec::<u64>::new();
+            let context = (&mut result as *mut Vec<u64>).cast();
+            assert!(sif_itree_query(tree, 4, 10, Some(collect), context));
+            assert_eq!(result, [10]);
+
+            result.clear();
+            let context = (&mut result as *mut Vec<u64>).cast();
+            assert!(!sif_itree_query(tree, 0, 10, Some(collect), context));
+            assert_eq!(result.len(), 2);
+
+            assert!(!sif_itree_query(tree, 0, 10, None, context));
+
+            sif_itree_free(tree);
+
+            let empty = sif_itree_new(std::ptr::null(), std::ptr::null(), std::ptr::null(), 0);
+            assert_eq!(sif_itree_len(empty), 0);
+            sif_itree_free(empty);
+            sif_itree_free(std::ptr::null_mut());
+        }
+    }
+
+    #[cfg(all(feature = "mmap", target_endian = "little", unix))]
+    #[test]
+    #[allow(unsafe_code)]
+    fn mmap_non_utf8_path_via_c_api() {
+        use std::ffi::CString;
+        use std::fs::{remove_file, File};
+        use std::process::id;
+
+        let mut path = std::env::temp_dir()
+            .join(format!("sif-itree-ffi-{}-", id()))
+            .as_os_str()
+            .as_bytes()
+            .to_vec();
+        path.push(0xff);
+        let path = CString::new(path).unwrap();
+
+        let tree = ITree::<u64, u64>::new([(0..5, 0), (1..2, 1)]);
+        tree.write_to(File::create(OsStr::from_bytes(path.as_bytes())).unwrap())
+            .unwrap();
+
+        // SAFETY: The path is NUL-terminated, the file is not modified and the tree is released exactly once.
+        unsafe {
+            let mapped = sif_itree_from_mmap(path.as_ptr());
+            assert!(!mapped.is_null());
+            assert_eq!(sif_itree_len(mapped), 2);
+            sif_itree_free(mapped);
+
+            assert!(sif_itree_from_mmap(std::ptr::null()).is_null());
+        }
+
+        remove_file(OsStr::from_bytes(path.as_bytes())).unwrap();
+    }
+
+    #[test]
+    #[allow(unsafe_code)]
+    fn raw_parts_roundtrip() {
+        let tree = ITree::<_, _>::new([(0..5, 'a'), (1..2, 'b')]);
+        let expected = tree.clone();
+
+        let (ptr, len) = tree.into_raw_parts();
+        // SAFETY: The parts were just obtained from `into_raw_parts`.
+        let tree = unsafe { ITree::from_raw_parts(ptr, len) };
+
+        assert_eq!(tree, expected);
+    }
+}
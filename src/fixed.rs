@@ -0,0 +1,106 @@
+#[cfg(feature = "heapless")]
+use core::error::Error;
+#[cfg(feature = "heapless")]
+use core::fmt;
+use core::marker::PhantomData;
+
+use crate::sort::{node, sort, update_max};
+use crate::{ITree, Item, Node};
+
+impl<K, V, const N: usize> ITree<K, V, [Node<K, V>; N]>
+where
+    K: Ord + Clone,
+{
+    /// Construct a new tree stored inline in an array by sorting the given `items`
+    pub fn from_array(items: [Item<K, V>; N]) -> Self {
+        let mut nodes = items.map(node);
+
+        sort(&mut nodes);
+
+        if N != 0 {
+            update_max(&mut nodes);
+        }
+
+        Self {
+            nodes,
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<K, V, const N: usize> ITree<K, V, heapless::Vec<Node<K, V>, N>>
+where
+    K: Ord + Clone,
+{
+    /// Construct a new tree with a fixed capacity by sorting the given `items`
+    ///
+    /// Requires the `heapless` feature. Fails without consuming further items if there are more than `N` items.
+    pub fn try_new_fixed<I>(items: I) -> Result<Self, CapacityExceeded>
+    where
+        I: IntoIterator<Item = Item<K, V>>,
+    {
+        let mut nodes = heapless::Vec::new();
+
+        for item in items {
+            if nodes.push(node(item)).is_err() {
+                return Err(CapacityExceeded(N));
+            }
+        }
+
+        sort(&mut nodes);
+
+        if !nodes.is_empty() {
+            update_max(&mut nodes);
+        }
+
+        Ok(Self {
+            nodes,
+            _marker: PhantomData,
+        })
+    }
+}
+
+/// Indicates that the items do not fit into a tree with the given fixed capacity
+#[cfg(feature = "heapless")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CapacityExceeded(pub usize);
+
+#[cfg(feature = "heapless")]
+impl fmt::Display for CapacityExceeded {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "tree can hold at most {} items", self.0)
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl Error for CapacityExceeded {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_array_does_not_allocate() {
+        let tree = ITree::from_array([(2..3, 2), (0..5, 0), (1..2, 1)]);
+        assert!(tree.is_valid());
+        assert!(tree.iter().eq(&[(0..5, 0), (1..2, 1), (2..3, 2)]));
+
+        let tree = ITree::<i32, (), _>::from_array([]);
+        assert!(tree.is_empty());
+    }
+
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn try_new_fixed_fails_on_overflow() {
+        let tree =
+            ITree::<_, _, heapless::Vec<_, 3>>::try_new_fixed([(1..2, 1), (0..1, 0)]).unwrap();
+        assert!(tree.is_valid());
+        assert!(tree.iter().eq(&[(0..1, 0), (1..2, 1)]));
+
+        assert_eq!(
+            ITree::<_, _, heapless::Vec<_, 1>>::try_new_fixed([(1..2, 1), (0..1, 0)]).unwrap_err(),
+            CapacityExceeded(1)
+        );
+    }
+}
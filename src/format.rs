@@ -0,0 +1,864 @@
+#[cfg(feature = "std")]
+use core::mem::offset_of;
+use core::mem::size_of;
+#[cfg(all(feature = "std", feature = "bytemuck"))]
+use core::mem::size_of_val;
+#[cfg(feature = "std")]
+use std::io::{self, Read, Write};
+#[cfg(feature = "std")]
+use std::marker::PhantomData;
+
+#[cfg(feature = "bytemuck")]
+use bytemuck::AnyBitPattern;
+#[cfg(all(feature = "std", feature = "bytemuck"))]
+use bytemuck::{pod_read_unaligned, NoUninit};
+
+#[cfg(all(feature = "std", feature = "bytemuck"))]
+use crate::bytes::{copy_fields, padding_free_bytes};
+#[cfg(feature = "bytemuck")]
+use crate::InvalidBytes;
+use crate::{Codec, ITree, Node};
+
+const MAGIC: [u8; 8] = *b"SIFITREE";
+const VERSION: u32 = 2;
+pub(crate) const HEADER_LEN: usize = 32;
+pub(crate) const CHECKSUM_LEN: usize = 4;
+#[cfg(all(feature = "std", feature = "bytemuck"))]
+const BULK_LEN: usize = 4096;
+
+#[cfg(feature = "std")]
+impl<K, V, S> ITree<K, V, S>
+where
+    K: Codec,
+    V: Codec,
+    S: AsRef<[Node<K, V>]>,
+{
+    /// Write the tree using the versioned binary format
+    ///
+    /// Requires the `std` feature. The format starts with a header of 32 bytes consisting of the magic bytes `SIFITREE`, the format version as a `u32`, the [tags][Codec::TAG] of `K` and `V` as one byte each, two reserved zero bytes, the number of nodes as a `u64`, the size of each node as a `u32` and four reserved zero bytes. The header is followed by the nodes in the `#[repr(C)]` layout of [`Node`] with each field encoded using [`Codec`] and zeroed padding and finally by a CRC-32C checksum as a `u32` covering the header and all nodes. All integers are little-endian. Trees too large for memory can be written in the same format using [`ITreeWriter`][crate::ITreeWriter].
+    ///
+    /// Fails with [`InvalidInput`][io::ErrorKind::InvalidInput] if the encoded representation of `K` or `V` is larger than their in-memory representation or if the [tag][Codec::TAG] of `K` or `V` is zero.
+    pub fn write_to<W>(&self, mut writer: W) -> io::Result<W>
+    where
+        W: Write,
+    {
+        let nodes = self.nodes.as_ref();
+
+        let header = encode_header::<K, V>(nodes.len())?;
+
+        let mut crc = Crc32c::new();
+        crc.update(&header);
+
+        writer.write_all(&header)?;
+
+        let mut buf = vec![0; size_of::<Node<K, V>>()];
+
+        for node in nodes {
+            encode_node(node, &mut buf);
+
+            crc.update(&buf);
+
+            writer.write_all(&buf)?;
+        }
+
+        writer.write_all(&crc.finish().to_le_bytes())?;
+
+        Ok(writer)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S> ITree<K, V, S>
+where
+    K: Ord + Codec,
+    V: Codec,
+    S: From<Vec<Node<K, V>>> + AsRef<[Node<K, V>]>,
+{
+    /// Read a tree written using [`write_to`][ITree::write_to]
+    ///
+    /// Requires the `std` feature. Fails with [`InvalidData`][io::ErrorKind::InvalidData] if the header does not match `K` and `V`, if the checksum does not match or if the nodes are not organized as an interval tree.
+    pub fn read_from<R>(mut reader: R) -> io::Result<Self>
+    where
+        R: Read,
+    {
+        let header = FormatHeader::read_from(&mut reader)?;
+
+        Self::read_nodes_from(header, reader)
+    }
+
+    /// Read a tree written using [`write_to`][ITree::write_to], passing files with a different version or layout to `upgrade`
+    ///
+    /// Requires the `std` feature. The `upgrade` hook receives the header and the `reader` positioned after it and can convert snapshots written by older versions of this crate or using different key and value types.
+    pub fn read_from_with<R, U>(mut reader: R, upgrade: U) -> io::Result<Self>
+    where
+        R: Read,
+        U: FnOnce(FormatHeader, R) -> io::Result<Self>,
+    {
+        let header = FormatHeader::read_from(&mut reader)?;
+
+        if header.matches::<K, V>() {
+            Self::read_nodes_from(header, reader)
+        } else {
+            upgrade(header, reader)
+        }
+    }
+
+    /// Read the nodes following an already read `header`
+    ///
+    /// Requires the `std` feature. Fails like [`read_from`][Self::read_from].
+    pub fn read_nodes_from<R>(header: FormatHeader, mut reader: R) -> io::Result<Self>
+    where
+        R: Read,
+    {
+        let len = header
+            .check::<K, V>()
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+        let mut nodes = Vec::new();
+        let mut buf = vec![0; size_of::<Node<K, V>>()];
+
+        let mut crc = Crc32c::new();
+        crc.update(&header.0);
+
+        for _ in 0..len {
+            reader.read_exact(&mut buf)?;
+
+            crc.update(&buf);
+
+            nodes.push(decode_node(&buf));
+        }
+
+        read_checksum(&mut reader, &crc)?;
+
+        crate::validate::validate(&nodes)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+        Ok(Self {
+            nodes: nodes.into(),
+            _marker: PhantomData,
+        })
+    }
+}
+
+/// The header of the versioned binary format written by [`write_to`][ITree::write_to]
+///
+/// Allows inspecting files before reading them, e.g. to dispatch to an upgrade path for older versions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FormatHeader([u8; HEADER_LEN]);
+
+impl FormatHeader {
+    /// The version of the format written by this version of the crate
+    pub const CURRENT_VERSION: u32 = VERSION;
+
+    /// The size of the header in bytes
+    pub const LEN: usize = HEADER_LEN;
+
+    /// Interpret the given `bytes` as a header, failing if they do not start with the magic bytes
+    pub fn new(bytes: [u8; Self::LEN]) -> Option<Self> {
+        (bytes[..8] == MAGIC).then_some(Self(bytes))
+    }
+
+    /// Read a header, failing with [`InvalidData`][io::ErrorKind::InvalidData] if it does not start with the magic bytes
+    ///
+    /// Requires the `std` feature.
+    #[cfg(feature = "std")]
+    pub fn read_from<R>(mut reader: R) -> io::Result<Self>
+    where
+        R: Read,
+    {
+        let mut bytes = [0; Self::LEN];
+        reader.read_exact(&mut bytes)?;
+
+        Self::new(bytes)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid magic bytes"))
+    }
+
+    /// The version of the format
+    pub fn version(&self) -> u32 {
+        u32::decode(&self.0[8..])
+    }
+
+    /// The [tag][Codec::TAG] of the key type
+    pub fn key_tag(&self) -> u8 {
+        self.0[12]
+    }
+
+    /// The [tag][Codec::TAG] of the value type
+    pub fn value_tag(&self) -> u8 {
+        self.0[13]
+    }
+
+    /// The number of nodes
+    pub fn len(&self) -> u64 {
+        u64::decode(&self.0[16..])
+    }
+
+    /// Whether the tree is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The size of each node in bytes
+    pub fn node_size(&self) -> u32 {
+        u32::decode(&self.0[24..])
+    }
+
+    /// Whether nodes with keys of type `K` and values of type `V` can be read using this version of the crate
+    pub fn matches<K, V>(&self) -> bool
+    where
+        K: Codec,
+        V: Codec,
+    {
+        self.check::<K, V>().is_ok()
+    }
+
+    fn check<K, V>(&self) -> Result<usize, &'static str>
+    where
+        K: Codec,
+        V: Codec,
+    {
+        if self.version() != VERSION {
+            return Err("unsupported format version");
+        }
+
+        if K::TAG == 0 || V::TAG == 0 {
+            return Err("key or value type without tag");
+        }
+
+        if self.key_tag() != K::TAG || self.value_tag() != V::TAG {
+            return Err("mismatched key or value type");
+        }
+
+        if self.node_size() as usize != size_of::<Node<K, V>>() {
+            return Err("mismatched node size");
+        }
+
+        if self.0[14..16] != [0; 2] || self.0[28..32] != [0; 4] {
+            return Err("reserved header bytes are not zero");
+        }
+
+        usize::try_from(self.len()).map_err(|_| "too many nodes")
+    }
+}
+
+#[cfg(all(feature = "std", feature = "bytemuck"))]
+impl<K, V, S> ITree<K, V, S>
+where
+    K: ZeroCopy + NoUninit,
+    V: ZeroCopy + NoUninit,
+    S: AsRef<[Node<K, V>]>,
+{
+    /// Write the tree using the versioned binary format, copying the nodes in bulk
+    ///
+    /// Requires the `std` and `bytemuck` features. Produces the same bytes and fails like [`write_to`][Self::write_to]. If the layout of `Node<K, V>` contains no padding, the nodes are written directly without copying them first.
+    pub fn write_bulk_to<W>(&self, mut writer: W) -> io::Result<W>
+    where
+        W: Write,
+    {
+        let nodes = self.nodes.as_ref();
+
+        let header = encode_header::<K, V>(nodes.len())?;
+
+        let mut crc = Crc32c::new();
+        crc.update(&header);
+
+        writer.write_all(&header)?;
+
+        if let Some(bytes) = padding_free_bytes(nodes) {
+            crc.update(bytes);
+
+            writer.write_all(bytes)?;
+        } else {
+            let mut buf = vec![0; BULK_LEN * size_of::<Node<K, V>>()];
+
+            for nodes in nodes.chunks(BULK_LEN) {
+                let buf = &mut buf[..size_of_val(nodes)];
+
+                copy_fields(nodes, buf);
+
+                crc.update(buf);
+
+                writer.write_all(buf)?;
+            }
+        }
+
+        writer.write_all(&crc.finish().to_le_bytes())?;
+
+        Ok(writer)
+    }
+}
+
+#[cfg(all(feature = "std", feature = "bytemuck"))]
+impl<K, V, S> ITree<K, V, S>
+where
+    K: Ord + ZeroCopy,
+    V: ZeroCopy,
+    S: From<Vec<Node<K, V>>> + AsRef<[Node<K, V>]>,
+{
+    /// Read a tree written using [`write_to`][ITree::write_to] or [`write_bulk_to`][ITree::write_bulk_to], copying the nodes in bulk
+    ///
+    /// Requires the `std` and `bytemuck` features. Fails like [`read_from`][Self::read_from].
+    pub fn read_bulk_from<R>(mut reader: R) -> io::Result<Self>
+    where
+        R: Read,
+    {
+        let header = FormatHeader::read_from(&mut reader)?;
+
+        let len = header
+            .check::<K, V>()
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+        let mut nodes = Vec::new();
+        let mut buf = vec![0; BULK_LEN * size_of::<Node<K, V>>()];
+
+        let mut crc = Crc32c::new();
+        crc.update(&header.0);
+
+        let mut remaining = len;
+
+        while remaining != 0 {
+            let chunk = remaining.min(BULK_LEN);
+            remaining -= chunk;
+
+            let buf = &mut buf[..chunk * size_of::<Node<K, V>>()];
+            reader.read_exact(buf)?;
+
+            crc.update(buf);
+
+            nodes.extend(
+                buf.chunks_exact(size_of::<Node<K, V>>())
+                    .map(pod_read_unaligned::<Node<K, V>>),
+            );
+        }
+
+        read_checksum(&mut reader, &crc)?;
+
+        crate::validate::validate(&nodes)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+        Ok(Self {
+            nodes: nodes.into(),
+            _marker: PhantomData,
+        })
+    }
+}
+
+/// Types whose in-memory representation matches their [`Codec`] encoding so that they can be loaded without copying
+///
+/// Requires the `bytemuck` feature. Implemented for the primitive types on little-endian targets and for [`LittleEndian`][crate::LittleEndian] integers on all targets.
+#[cfg(feature = "bytemuck")]
+pub trait ZeroCopy: Codec + AnyBitPattern {}
+
+#[cfg(feature = "bytemuck")]
+macro_rules! impl_zero_copy {
+    ($($type:ty),*) => {
+        $(
+            impl ZeroCopy for $type {}
+        )*
+    };
+}
+
+#[cfg(feature = "bytemuck")]
+impl_zero_copy!(
+    (),
+    u8,
+    i8,
+    crate::LittleEndian<u16>,
+    crate::LittleEndian<u32>,
+    crate::LittleEndian<u64>,
+    crate::LittleEndian<u128>,
+    crate::LittleEndian<i16>,
+    crate::LittleEndian<i32>,
+    crate::LittleEndian<i64>,
+    crate::LittleEndian<i128>
+);
+
+#[cfg(all(feature = "bytemuck", target_endian = "little"))]
+impl_zero_copy!(u16, u32, u64, u128, i16, i32, i64, i128, f32, f64);
+
+#[cfg(feature = "bytemuck")]
+impl<'a, K, V> ITree<K, V, &'a [Node<K, V>]>
+where
+    K: Ord + ZeroCopy,
+    V: ZeroCopy,
+{
+    /// Interprets bytes written using [`write_to`][ITree::write_to] as a tree without copying the nodes
+    ///
+    /// Requires the `bytemuck` feature. On big-endian targets, this is only possible using [`LittleEndian`][crate::LittleEndian] keys and values. The `bytes` must be aligned suitably for `Node<K, V>`, which is the case for memory maps and can be ensured using [`AlignedBytes`][crate::AlignedBytes] otherwise. Trailing bytes after the checksum are ignored. Verifying the checksum takes time linear in the size of the tree, like checking its invariants.
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, InvalidBytes> {
+        let (header, nodes) = bytes
+            .split_first_chunk::<HEADER_LEN>()
+            .ok_or(InvalidBytes::Truncated)?;
+
+        let len = FormatHeader::new(*header)
+            .ok_or(InvalidBytes::InvalidHeader)?
+            .check::<K, V>()
+            .map_err(|_| InvalidBytes::InvalidHeader)?;
+
+        let (nodes, checksum) = len
+            .checked_mul(size_of::<Node<K, V>>())
+            .and_then(|len| nodes.split_at_checked(len))
+            .ok_or(InvalidBytes::Truncated)?;
+
+        let checksum = checksum
+            .first_chunk::<CHECKSUM_LEN>()
+            .ok_or(InvalidBytes::Truncated)?;
+
+        let mut crc = Crc32c::new();
+        crc.update(header);
+        crc.update(nodes);
+
+        if u32::from_le_bytes(*checksum) != crc.finish() {
+            return Err(InvalidBytes::ChecksumMismatch);
+        }
+
+        Self::from_pod_bytes(nodes)
+    }
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn encode_header<K, V>(len: usize) -> io::Result<[u8; HEADER_LEN]>
+where
+    K: Codec,
+    V: Codec,
+{
+    if K::SIZE > size_of::<K>() || V::SIZE > size_of::<V>() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "encoded representation does not fit into node layout",
+        ));
+    }
+
+    if K::TAG == 0 || V::TAG == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "key or value type without tag",
+        ));
+    }
+
+    let mut header = [0; HEADER_LEN];
+
+    header[..8].copy_from_slice(&MAGIC);
+    VERSION.encode(&mut header[8..]);
+    header[12] = K::TAG;
+    header[13] = V::TAG;
+    (len as u64).encode(&mut header[16..]);
+    (size_of::<Node<K, V>>() as u32).encode(&mut header[24..]);
+
+    Ok(header)
+}
+
+pub(crate) struct Crc32c(u32);
+
+impl Crc32c {
+    /// Tables for processing eight bytes at once, where `TABLES[k][byte]` is the checksum of `byte` followed by `k` zero bytes
+    const TABLES: [[u32; 256]; 8] = {
+        let mut tables = [[0; 256]; 8];
+
+        let mut index = 0;
+
+        while index < 256 {
+            let mut crc = index as u32;
+
+            let mut bit = 0;
+
+            while bit < 8 {
+                crc = if crc & 1 != 0 {
+                    (crc >> 1) ^ 0x82f6_3b78
+                } else {
+                    crc >> 1
+                };
+
+                bit += 1;
+            }
+
+            tables[0][index] = crc;
+            index += 1;
+        }
+
+        let mut table = 1;
+
+        while table < 8 {
+            let mut index = 0;
+
+            while index < 256 {
+                let crc = tables[table - 1][index];
+
+                tables[table][index] = (crc >> 8) ^ tables[0][(crc & 0xff) as usize];
+                index += 1;
+            }
+
+            table += 1;
+        }
+
+        tables
+    };
+
+    pub(crate) fn new() -> Self {
+        Self(!0)
+    }
+
+    pub(crate) fn update(&mut self, bytes: &[u8]) {
+        let [t0, t1, t2, t3, t4, t5, t6, t7] = &Self::TABLES;
+
+        let mut chunks = bytes.chunks_exact(8);
+
+        for chunk in &mut chunks {
+            let lo = (self.0 ^ u32::decode(chunk)).to_le_bytes();
+            let hi = u32::decode(&chunk[4..]).to_le_bytes();
+
+            self.0 = t7[lo[0] as usize]
+                ^ t6[lo[1] as usize]
+                ^ t5[lo[2] as usize]
+                ^ t4[lo[3] as usize]
+                ^ t3[hi[0] as usize]
+                ^ t2[hi[1] as usize]
+                ^ t1[hi[2] as usize]
+                ^ t0[hi[3] as usize];
+        }
+
+        for &byte in chunks.remainder() {
+            self.0 = t0[((self.0 ^ u32::from(byte)) & 0xff) as usize] ^ (self.0 >> 8);
+        }
+    }
+
+    pub(crate) fn finish(&self) -> u32 {
+        !self.0
+    }
+}
+
+#[cfg(feature = "std")]
+fn read_checksum<R>(mut reader: R, crc: &Crc32c) -> io::Result<()>
+where
+    R: Read,
+{
+    let mut checksum = [0; CHECKSUM_LEN];
+    reader.read_exact(&mut checksum)?;
+
+    if u32::from_le_bytes(checksum) != crc.finish() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "checksum mismatch",
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn encode_node<K, V>(node: &Node<K, V>, buf: &mut [u8])
+where
+    K: Codec,
+    V: Codec,
+{
+    node.start.encode(&mut buf[offset_of!(Node<K, V>, start)..]);
+    node.end.encode(&mut buf[offset_of!(Node<K, V>, end)..]);
+    node.max.encode(&mut buf[offset_of!(Node<K, V>, max)..]);
+    node.value.encode(&mut buf[offset_of!(Node<K, V>, value)..]);
+}
+
+#[cfg(feature = "std")]
+fn decode_node<K, V>(buf: &[u8]) -> Node<K, V>
+where
+    K: Codec,
+    V: Codec,
+{
+    Node {
+        start: K::decode(&buf[offset_of!(Node<K, V>, start)..]),
+        end: K::decode(&buf[offset_of!(Node<K, V>, end)..]),
+        max: K::decode(&buf[offset_of!(Node<K, V>, max)..]),
+        value: V::decode(&buf[offset_of!(Node<K, V>, value)..]),
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    use std::io::Cursor;
+
+    #[test]
+    fn write_and_read_tree() {
+        let tree = ITree::<u16, u64>::new([(0..5, 0), (1..2, 1), (2..3, 2)]);
+
+        let buf = tree.write_to(Vec::new()).unwrap();
+        assert_eq!(
+            buf.len(),
+            HEADER_LEN + 3 * size_of::<Node<u16, u64>>() + CHECKSUM_LEN
+        );
+        assert_eq!(&buf[..8], b"SIFITREE");
+
+        let read = ITree::<u16, u64>::read_from(Cursor::new(&buf)).unwrap();
+        assert_eq!(read.as_inner(), tree.as_inner());
+
+        let err = ITree::<u16, u32>::read_from(Cursor::new(&buf)).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        let mut corrupted = buf.clone();
+        corrupted[HEADER_LEN + offset_of!(Node<u16, u64>, value)] = 1;
+        let err = ITree::<u16, u64>::read_from(Cursor::new(&corrupted)).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(err.to_string(), "checksum mismatch");
+
+        let mut corrupted = buf.clone();
+        corrupted[16] = 2;
+        let err = ITree::<u16, u64>::read_from(Cursor::new(&corrupted)).unwrap_err();
+        assert_eq!(err.to_string(), "checksum mismatch");
+
+        let err = ITree::<u16, u64>::read_from(Cursor::new(&buf[..buf.len() - 1])).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+
+        let mut corrupted = buf.clone();
+        corrupted[HEADER_LEN + offset_of!(Node<u16, u64>, max)] = 1;
+        update_checksum(&mut corrupted);
+        let err = ITree::<u16, u64>::read_from(Cursor::new(&corrupted)).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(err.to_string(), "node 0 has an invalid maximum end");
+    }
+
+    fn update_checksum(buf: &mut [u8]) {
+        let (contents, checksum) = buf.split_at_mut(buf.len() - CHECKSUM_LEN);
+
+        let mut crc = Crc32c::new();
+        crc.update(contents);
+        checksum.copy_from_slice(&crc.finish().to_le_bytes());
+    }
+
+    #[test]
+    fn upgrade_mismatched_files() {
+        let tree = ITree::<u16, u64>::new([(0..5, 0), (1..2, 1), (2..3, 2)]);
+
+        let buf = tree.write_to(Vec::new()).unwrap();
+
+        let header = FormatHeader::read_from(Cursor::new(&buf)).unwrap();
+        assert_eq!(header.version(), FormatHeader::CURRENT_VERSION);
+        assert_eq!((header.key_tag(), header.value_tag()), (2, 4));
+        assert_eq!(header.len(), 3);
+        assert!(header.matches::<u16, u64>());
+        assert!(!header.matches::<u16, u32>());
+
+        let err = ITree::<u16, u32>::read_from(Cursor::new(&buf)).unwrap_err();
+        assert_eq!(err.to_string(), "mismatched key or value type");
+
+        let read = ITree::<u16, u32>::read_from_with(Cursor::new(&buf), |header, reader| {
+            let tree = ITree::<u16, u64>::read_nodes_from(header, reader)?;
+
+            Ok(ITree::new(
+                tree.iter()
+                    .map(|node| (node.start..node.end, node.value as u32)),
+            ))
+        })
+        .unwrap();
+        assert!(read
+            .iter()
+            .zip(tree.iter())
+            .all(|(lhs, rhs)| lhs.value as u64 == rhs.value));
+
+        let mut old = buf.clone();
+        old[8] = 0;
+        let err = ITree::<u16, u64>::read_from(Cursor::new(&old)).unwrap_err();
+        assert_eq!(err.to_string(), "unsupported format version");
+
+        old[0] = 0;
+        let err = FormatHeader::read_from(Cursor::new(&old)).unwrap_err();
+        assert_eq!(err.to_string(), "invalid magic bytes");
+    }
+
+    #[cfg(all(feature = "bytemuck", target_endian = "little"))]
+    #[test]
+    fn zero_copy_from_bytes() {
+        use crate::AlignedBytes;
+
+        let tree = ITree::<u16, u64>::new([(0..5, 0), (1..2, 1), (2..3, 2)]);
+
+        let buf = tree.write_to(Vec::new()).unwrap();
+
+        let mut aligned = AlignedBytes::<u16, u64, _>::new([0; 84]);
+        aligned.bytes.copy_from_slice(&buf);
+
+        let loaded = ITree::<u16, u64, _>::from_bytes(&aligned.bytes).unwrap();
+        assert_eq!(loaded.as_inner(), &&*tree.nodes);
+
+        assert_eq!(
+            ITree::<u16, u32, _>::from_bytes(&aligned.bytes).unwrap_err(),
+            InvalidBytes::InvalidHeader
+        );
+        assert_eq!(
+            ITree::<u16, u64, _>::from_bytes(&aligned.bytes[..64]).unwrap_err(),
+            InvalidBytes::Truncated
+        );
+        assert_eq!(
+            ITree::<u16, u64, _>::from_bytes(&aligned.bytes[..80]).unwrap_err(),
+            InvalidBytes::Truncated
+        );
+
+        aligned.bytes[HEADER_LEN] ^= 1;
+        assert_eq!(
+            ITree::<u16, u64, _>::from_bytes(&aligned.bytes).unwrap_err(),
+            InvalidBytes::ChecksumMismatch
+        );
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn bulk_write_and_read() {
+        use crate::LittleEndian;
+
+        let tree = ITree::<LittleEndian<u32>, u8>::new((0..10_000).map(|idx| {
+            (
+                LittleEndian::new(idx)..LittleEndian::new(idx + 3),
+                idx as u8,
+            )
+        }));
+
+        let buf = tree.write_to(Vec::new()).unwrap();
+        let bulk = tree.write_bulk_to(Vec::new()).unwrap();
+        assert_eq!(buf, bulk);
+
+        let read = ITree::<LittleEndian<u32>, u8>::read_from(Cursor::new(&bulk)).unwrap();
+        assert_eq!(read.as_inner(), tree.as_inner());
+
+        let read = ITree::<LittleEndian<u32>, u8>::read_bulk_from(Cursor::new(&buf)).unwrap();
+        assert_eq!(read.as_inner(), tree.as_inner());
+
+        let mut corrupted = buf.clone();
+        corrupted[HEADER_LEN] ^= 1;
+        let err =
+            ITree::<LittleEndian<u32>, u8>::read_bulk_from(Cursor::new(&corrupted)).unwrap_err();
+        assert_eq!(err.to_string(), "checksum mismatch");
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn bulk_write_without_padding() {
+        use crate::LittleEndian;
+
+        let tree = ITree::<LittleEndian<u32>, LittleEndian<u32>>::new((0..10_000).map(|idx| {
+            (
+                LittleEndian::new(idx)..LittleEndian::new(idx + 3),
+                LittleEndian::new(idx),
+            )
+        }));
+
+        let buf = tree.write_to(Vec::new()).unwrap();
+        let bulk = tree.write_bulk_to(Vec::new()).unwrap();
+        assert_eq!(buf, bulk);
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    struct Untagged(u16);
+
+    impl Codec for Untagged {
+        const SIZE: usize = 2;
+
+        fn encode(&self, buf: &mut [u8]) {
+            self.0.encode(buf);
+        }
+
+        fn decode(buf: &[u8]) -> Self {
+            Self(u16::decode(buf))
+        }
+    }
+
+    #[test]
+    fn reject_untagged_types() {
+        let tree = ITree::<u16, u64>::new([(0..5, 0), (1..2, 1), (2..3, 2)]);
+
+        let mut buf = tree.write_to(Vec::new()).unwrap();
+        buf[12] = 0;
+        update_checksum(&mut buf);
+
+        let header = FormatHeader::read_from(Cursor::new(&buf)).unwrap();
+        assert!(!header.matches::<Untagged, u64>());
+
+        let err = ITree::<Untagged, u64>::read_from(Cursor::new(&buf)).unwrap_err();
+        assert_eq!(err.to_string(), "key or value type without tag");
+    }
+
+    #[test]
+    fn cannot_write_untagged_types() {
+        let tree = ITree::<Untagged, u64>::new([(Untagged(0)..Untagged(5), 0)]);
+
+        let err = tree.write_to(Vec::new()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(err.to_string(), "key or value type without tag");
+    }
+
+    #[test]
+    fn reject_reserved_header_bytes() {
+        let tree = ITree::<u16, u64>::new([(0..5, 0), (1..2, 1), (2..3, 2)]);
+
+        let buf = tree.write_to(Vec::new()).unwrap();
+
+        for index in [14, 15, 28, 31] {
+            let mut buf = buf.clone();
+            buf[index] = 1;
+            update_checksum(&mut buf);
+
+            let header = FormatHeader::read_from(Cursor::new(&buf)).unwrap();
+            assert!(!header.matches::<u16, u64>());
+
+            let err = ITree::<u16, u64>::read_from(Cursor::new(&buf)).unwrap_err();
+            assert_eq!(err.to_string(), "reserved header bytes are not zero");
+        }
+    }
+
+    #[test]
+    fn crc32c_check_value() {
+        let mut crc = Crc32c::new();
+        crc.update(b"123456789");
+        assert_eq!(crc.finish(), 0xe306_9283);
+
+        let mut crc = Crc32c::new();
+        crc.update(&[0; 32]);
+        assert_eq!(crc.finish(), 0x8a91_36aa);
+
+        let mut crc = Crc32c::new();
+        crc.update(&[0xff; 32]);
+        assert_eq!(crc.finish(), 0x62a8_ab43);
+
+        let bytes = (0..=255).collect::<Vec<u8>>();
+
+        for split in [0, 1, 7, 8, 13, 255] {
+            let mut crc = Crc32c::new();
+            crc.update(&bytes[..split]);
+            crc.update(&bytes[split..]);
+
+            let mut expected = Crc32c::new();
+            for byte in &bytes {
+                expected.update(std::slice::from_ref(byte));
+            }
+
+            assert_eq!(crc.finish(), expected.finish());
+        }
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn zero_copy_little_endian() {
+        use std::ops::ControlFlow;
+
+        use crate::{AlignedBytes, LittleEndian};
+
+        let tree = ITree::<u32, u32>::new([(0..5, 0), (1..2, 1), (2..3, 2)]);
+
+        let buf = tree.write_to(Vec::new()).unwrap();
+
+        let mut aligned = AlignedBytes::<u32, u32, _>::new([0; 84]);
+        aligned.bytes.copy_from_slice(&buf);
+
+        let loaded =
+            ITree::<LittleEndian<u32>, LittleEndian<u32>, _>::from_bytes(&aligned.bytes).unwrap();
+
+        let mut result = Vec::new();
+        let _ = loaded.query(LittleEndian::new(1)..LittleEndian::new(3), |entry| {
+            result.push(entry.value().get());
+            ControlFlow::Continue(())
+        });
+        result.sort_unstable();
+        assert_eq!(result, [0, 1, 2]);
+
+        let buf2 = loaded.write_to(Vec::new()).unwrap();
+        assert_eq!(buf, buf2);
+    }
+}
@@ -0,0 +1,276 @@
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::mem::size_of;
+use core::ops::{ControlFlow, Range};
+
+#[cfg(feature = "serde")]
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize};
+
+use crate::{Entry, ITree, InvalidTree, Item, Node};
+
+/// Interval tree whose nodes store a `u32` index into a separate array of values
+///
+/// Keeps the nodes small and dense if `V` is large, without having to box the values individually.
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(serialize = "ITree<K, u32, S>: Serialize, V: Serialize"))
+)]
+pub struct IndexedITree<K, V, S = Box<[Node<K, u32>]>> {
+    tree: ITree<K, u32, S>,
+    values: Box<[V]>,
+}
+
+/// Interval tree with `u32` bounds whose nodes store a `u32` index into a separate array of values
+///
+/// The nodes have a guaranteed size of 16 bytes independently of `V`, which suits e.g. genomic coordinates.
+pub type ITree32<V, S = Box<[Node<u32, u32>]>> = IndexedITree<u32, V, S>;
+
+const _: () = assert!(size_of::<Node<u32, u32>>() == 16);
+
+impl<K, V, S> IndexedITree<K, V, S>
+where
+    K: Ord + Clone,
+    S: AsMut<[Node<K, u32>]> + FromIterator<Node<K, u32>>,
+{
+    /// Construct a new tree by sorting the given `items`
+    ///
+    /// The values are stored in the order in which they were yielded by `items`.
+    ///
+    /// # Panics
+    ///
+    /// If there are more than `u32::MAX` items.
+    pub fn new<I>(items: I) -> Self
+    where
+        I: IntoIterator<Item = Item<K, V>>,
+    {
+        let mut values = Vec::new();
+
+        let tree = ITree::new(items.into_iter().map(|(interval, value)| {
+            let index = u32::try_from(values.len()).expect("too many items");
+            values.push(value);
+
+            (interval, index)
+        }));
+
+        Self {
+            tree,
+            values: values.into(),
+        }
+    }
+
+    /// Construct a new tree by sorting the given `items`, storing each distinct value only once
+    ///
+    /// The nodes then store codes into a dictionary of values ordered by their first occurrence, which saves memory if the values repeat often. Queries resolve the codes transparently.
+    ///
+    /// # Panics
+    ///
+    /// If there are more than `u32::MAX` distinct values.
+    pub fn new_interned<I>(items: I) -> Self
+    where
+        V: Ord,
+        I: IntoIterator<Item = Item<K, V>>,
+    {
+        let mut dictionary = BTreeMap::new();
+
+        let tree = ITree::new(items.into_iter().map(|(interval, value)| {
+            let code = u32::try_from(dictionary.len()).expect("too many distinct values");
+            let code = *dictionary.entry(value).or_insert(code);
+
+            (interval, code)
+        }));
+
+        let mut values = dictionary.into_iter().collect::<Vec<_>>();
+        values.sort_unstable_by_key(|(_, code)| *code);
+
+        Self {
+            tree,
+            values: values.into_iter().map(|(value, _)| value).collect(),
+        }
+    }
+}
+
+impl<K, V, S> IndexedITree<K, V, S>
+where
+    S: AsRef<[Node<K, u32>]>,
+{
+    /// The number of items in the tree
+    pub fn len(&self) -> usize {
+        self.tree.len()
+    }
+
+    /// Whether the tree contains no items
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+
+    /// The tree of intervals mapped to indices into [`values`][Self::values]
+    pub fn tree(&self) -> &ITree<K, u32, S> {
+        &self.tree
+    }
+
+    /// The values in the order in which they were inserted, or the distinct values if [interned][Self::new_interned]
+    pub fn values(&self) -> &[V] {
+        &self.values
+    }
+
+    /// Reassemble a tree from a tree of indices and the `values` they refer to, verifying that all indices are in bounds
+    pub fn from_parts(tree: ITree<K, u32, S>, values: Box<[V]>) -> Result<Self, InvalidTree> {
+        if let Some(index) = tree
+            .iter()
+            .position(|node| node.value as usize >= values.len())
+        {
+            return Err(InvalidTree::DanglingValue(index));
+        }
+
+        Ok(Self { tree, values })
+    }
+
+    /// Iterate over all items in the order of their interval starts
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = Entry<'_, K, V>> + ExactSizeIterator {
+        self.tree
+            .iter()
+            .map(|node| Entry::new(&node.start, &node.end, &self.values[node.value as usize]))
+    }
+
+    /// Query for all intervals overlapping the given interval
+    pub fn query<'a, H>(&'a self, interval: Range<K>, mut handler: H) -> ControlFlow<()>
+    where
+        K: Ord,
+        H: FnMut(Entry<'a, K, V>) -> ControlFlow<()>,
+    {
+        self.tree.query(interval, |entry| {
+            handler(Entry::new(
+                entry.start(),
+                entry.end(),
+                &self.values[*entry.value() as usize],
+            ))
+        })
+    }
+}
+
+/// Rejects nodes referring to missing values and [checks][ITree::check] the tree of indices
+#[cfg(feature = "serde")]
+impl<'de, K, V, S> Deserialize<'de> for IndexedITree<K, V, S>
+where
+    ITree<K, u32, S>: Deserialize<'de>,
+    V: Deserialize<'de>,
+    S: AsRef<[Node<K, u32>]>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let Parts { tree, values } = Parts::deserialize(deserializer)?;
+
+        Self::from_parts(tree, values).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+#[serde(
+    rename = "IndexedITree",
+    bound(deserialize = "ITree<K, u32, S>: Deserialize<'de>, V: Deserialize<'de>")
+)]
+struct Parts<K, V, S> {
+    tree: ITree<K, u32, S>,
+    values: Box<[V]>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn query_values_out_of_line() {
+        let tree =
+            IndexedITree::<_, _>::new([(5..10, [1_u8; 64]), (0..3, [2; 64]), (2..7, [3; 64])]);
+
+        assert_eq!(tree.len(), 3);
+        assert_eq!(tree.values()[0], [1; 64]);
+
+        let mut result = Vec::new();
+        let _ = tree.query(4..6, |entry| {
+            result.push(entry.value()[0]);
+            ControlFlow::Continue(())
+        });
+        assert_eq!(result, [3, 1]);
+
+        assert!(tree.iter().map(|entry| entry.value()[0]).eq([2, 3, 1]));
+    }
+
+    #[test]
+    fn query_interned_values() {
+        let tree = IndexedITree::<_, _>::new_interned([
+            (5..10, "exon".to_owned()),
+            (0..3, "intron".to_owned()),
+            (2..7, "exon".to_owned()),
+            (8..9, "intron".to_owned()),
+        ]);
+
+        assert_eq!(tree.len(), 4);
+        assert_eq!(tree.values(), ["exon", "intron"]);
+
+        let mut result = Vec::new();
+        let _ = tree.query(4..9, |entry| {
+            result.push(entry.value().as_str());
+            ControlFlow::Continue(())
+        });
+        result.sort_unstable();
+        assert_eq!(result, ["exon", "exon", "intron"]);
+
+        assert!(tree
+            .iter()
+            .map(|entry| entry.value().as_str())
+            .eq(["intron", "exon", "exon", "intron"]));
+    }
+
+    #[test]
+    fn query_genomic_coordinates() {
+        let tree = ITree32::<_>::new([
+            (11_873..14_409, "DDX11L1"),
+            (14_404..29_570, "WASH7P"),
+            (69_091..70_008, "OR4F5"),
+        ]);
+
+        let mut result = Vec::new();
+        let _ = tree.query(14_000..15_000, |entry| {
+            result.push(*entry.value());
+            ControlFlow::Continue(())
+        });
+        result.sort_unstable();
+        assert_eq!(result, ["DDX11L1", "WASH7P"]);
+    }
+
+    #[test]
+    fn from_parts_rejects_dangling_values() {
+        let tree = IndexedITree::<_, _>::new([(0..5, 'a'), (1..2, 'b')]);
+
+        let other = IndexedITree::from_parts(tree.tree().clone(), tree.values().into()).unwrap();
+        assert!(other.iter().map(|entry| *entry.value()).eq(['a', 'b']));
+
+        assert_eq!(
+            IndexedITree::from_parts(tree.tree().clone(), ['a'].into()).unwrap_err(),
+            InvalidTree::DanglingValue(1)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_rejects_dangling_values() {
+        let tree = IndexedITree::<u32, char>::new([(0..5, 'a'), (1..2, 'b')]);
+
+        let json = serde_json::to_string(&tree).unwrap();
+        let other = serde_json::from_str::<IndexedITree<u32, char>>(&json).unwrap();
+        assert!(other.iter().map(|entry| *entry.value()).eq(['a', 'b']));
+
+        let err = serde_json::from_str::<IndexedITree<u32, char>>(
+            r#"{"tree":[{"start":0,"end":5,"value":0},{"start":1,"end":2,"value":7}],"values":["a","b"]}"#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("node 1 refers to a missing value"));
+    }
+}
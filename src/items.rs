@@ -0,0 +1,95 @@
+//! Serialize trees as their items only, rebuilding them on deserialization
+//!
+//! Requires the `serde` feature. Use via `#[serde(with = "sif_itree::items")]` on fields of type [`ITree`]. The maximum of the interval upper bounds and the layout are never read from the input, so hand-edited or untrusted data cannot produce a tree which violates its invariants.
+
+use alloc::vec::Vec;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{ITree, Item, Node};
+
+/// Serialize the `tree` as a sequence of `(interval, value)` items
+pub fn serialize<K, V, S, Ser>(
+    tree: &ITree<K, V, S>,
+    serializer: Ser,
+) -> Result<Ser::Ok, Ser::Error>
+where
+    K: Serialize,
+    V: Serialize,
+    S: AsRef<[Node<K, V>]>,
+    Ser: Serializer,
+{
+    serializer.collect_seq(
+        tree.nodes
+            .as_ref()
+            .iter()
+            .map(|node| (&node.start..&node.end, &node.value)),
+    )
+}
+
+/// Deserialize a sequence of `(interval, value)` items and construct a tree from them via [`ITree::new`]
+pub fn deserialize<'de, K, V, S, D>(deserializer: D) -> Result<ITree<K, V, S>, D::Error>
+where
+    K: Ord + Clone + Deserialize<'de>,
+    V: Deserialize<'de>,
+    S: AsMut<[Node<K, V>]> + FromIterator<Node<K, V>>,
+    D: Deserializer<'de>,
+{
+    let items = Vec::<Item<K, V>>::deserialize(deserializer)?;
+
+    Ok(ITree::new(items))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct Annotations {
+        #[serde(with = "crate::items")]
+        tree: ITree<u32, char>,
+    }
+
+    #[test]
+    fn roundtrip() {
+        let annotations = Annotations {
+            tree: ITree::new([(2..3, 'c'), (0..5, 'a'), (1..2, 'b')]),
+        };
+
+        let json = serde_json::to_string(&annotations).unwrap();
+        assert_eq!(
+            json,
+            r#"{"tree":[[{"start":0,"end":5},"a"],[{"start":1,"end":2},"b"],[{"start":2,"end":3},"c"]]}"#
+        );
+
+        let other = serde_json::from_str::<Annotations>(&json).unwrap();
+        assert!(other.tree.is_valid());
+        assert_eq!(other.tree, annotations.tree);
+    }
+
+    #[test]
+    fn rebuild_hand_edited_input() {
+        let mut annotations = Annotations {
+            tree: ITree::new([(0..5, 'a'), (1..2, 'b'), (2..3, 'c')]),
+        };
+
+        for node in annotations.tree.as_mut() {
+            node.max = 0;
+        }
+        assert!(!annotations.tree.is_valid());
+
+        let json = serde_json::to_string(&annotations).unwrap();
+        let other = serde_json::from_str::<Annotations>(&json).unwrap();
+        assert!(other.tree.is_valid());
+        assert_eq!(other.tree.max_ends().collect::<Vec<_>>(), [&5, &5, &3]);
+
+        let other = serde_json::from_str::<Annotations>(
+            r#"{"tree":[[{"start":2,"end":3},"c"],[{"start":0,"end":5},"a"],[{"start":1,"end":2},"b"]]}"#,
+        )
+        .unwrap();
+        assert!(other.tree.is_valid());
+        assert_eq!(other.tree, annotations.tree);
+    }
+}
@@ -0,0 +1,120 @@
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::{ITree, Node};
+
+impl<K, V, S> ITree<K, V, S>
+where
+    K: Ord,
+    S: AsRef<[Node<K, V>]>,
+{
+    /// Find all pairs of overlapping query intervals and items, returned as parallel arrays of indices into the `queries` and the [`nodes`][Self::nodes]
+    ///
+    /// Such columnar results can be turned into a data frame by gathering the rows of both sides at the returned indices. Pairs are ordered by query and then by node.
+    pub fn overlap_join<I>(&self, queries: I) -> (Vec<usize>, Vec<usize>)
+    where
+        I: IntoIterator<Item = Range<K>>,
+    {
+        let nodes = self.nodes.as_ref();
+
+        let mut query_indices = Vec::new();
+        let mut node_indices = Vec::new();
+
+        if !nodes.is_empty() {
+            for (query_index, interval) in queries.into_iter().enumerate() {
+                join(&interval, nodes, 0, &mut node_indices);
+
+                query_indices.resize(node_indices.len(), query_index);
+            }
+        }
+
+        (query_indices, node_indices)
+    }
+}
+
+fn join<K, V>(interval: &Range<K>, nodes: &[Node<K, V>], offset: usize, indices: &mut Vec<usize>)
+where
+    K: Ord,
+{
+    let (left, [mid, right @ ..]) = nodes.split_at(nodes.len() / 2) else {
+        unreachable!()
+    };
+
+    if interval.start >= mid.max {
+        return;
+    }
+
+    if !left.is_empty() {
+        join(interval, left, offset, indices);
+    }
+
+    if interval.end > mid.start {
+        let index = offset + left.len();
+
+        if interval.start < mid.end {
+            indices.push(index);
+        }
+
+        if !right.is_empty() {
+            join(interval, right, index + 1, indices);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::ops::ControlFlow;
+
+    use proptest::{collection::vec, test_runner::TestRunner};
+
+    #[test]
+    fn overlap_join_matches_queries() {
+        TestRunner::default()
+            .run(
+                &(
+                    vec((0_i32..100, 0_i32..10), 0..100),
+                    vec((0_i32..100, 0_i32..10), 0..10),
+                ),
+                |(items, queries)| {
+                    let tree = ITree::<_, _>::new(
+                        items
+                            .iter()
+                            .enumerate()
+                            .map(|(idx, &(start, len))| (start..start + len, idx)),
+                    );
+
+                    let queries = queries
+                        .iter()
+                        .map(|&(start, len)| start..start + len)
+                        .collect::<Vec<_>>();
+
+                    let (query_indices, node_indices) = tree.overlap_join(queries.iter().cloned());
+                    assert_eq!(query_indices.len(), node_indices.len());
+
+                    for (query_index, interval) in queries.iter().enumerate() {
+                        let mut expected = Vec::new();
+                        let _ = tree.query(interval.clone(), |entry| {
+                            expected.push(*entry.value());
+                            ControlFlow::Continue(())
+                        });
+
+                        let mut actual = query_indices
+                            .iter()
+                            .zip(&node_indices)
+                            .filter(|(&lhs, _)| lhs == query_index)
+                            .map(|(_, &rhs)| tree[rhs].value)
+                            .collect::<Vec<_>>();
+
+                        expected.sort_unstable();
+                        actual.sort_unstable();
+                        assert_eq!(expected, actual);
+                    }
+
+                    Ok(())
+                },
+            )
+            .unwrap();
+    }
+}
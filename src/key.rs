@@ -0,0 +1,44 @@
+/// Primitive integer types used as interval bounds by specialized traversals
+///
+/// This trait is sealed and implemented for the primitive integer types only.
+pub trait PrimitiveKey: Copy + Ord + sealed::Sealed {}
+
+pub(crate) mod sealed {
+    pub trait Sealed: Sized {
+        fn offset_from(self, base: Self) -> Option<u32>;
+
+        fn add_offset(self, offset: u32) -> Self;
+
+        fn to_i128(self) -> i128;
+
+        fn from_i128(value: i128) -> Option<Self>;
+    }
+}
+
+macro_rules! impl_primitive_key {
+    ($($ty:ty),*) => {
+        $(
+            impl sealed::Sealed for $ty {
+                fn offset_from(self, base: Self) -> Option<u32> {
+                    u32::try_from(self as i128 - base as i128).ok()
+                }
+
+                fn add_offset(self, offset: u32) -> Self {
+                    (self as i128 + offset as i128) as Self
+                }
+
+                fn to_i128(self) -> i128 {
+                    self as i128
+                }
+
+                fn from_i128(value: i128) -> Option<Self> {
+                    Self::try_from(value).ok()
+                }
+            }
+
+            impl PrimitiveKey for $ty {}
+        )*
+    };
+}
+
+impl_primitive_key!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
@@ -0,0 +1,51 @@
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use crate::{ITree, Item, Node};
+
+impl<K, V, S> ITree<K, V, S>
+where
+    K: Ord + Clone,
+    S: AsMut<[Node<K, V>]> + FromIterator<Node<K, V>>,
+{
+    /// Construct one tree per key, e.g. per contig or chromosome, by grouping and sorting the given `items`
+    ///
+    /// Genomic intervals like `bio_types::genome::Interval` map to items as `(interval.contig().to_owned(), (interval.range().clone(), value))`.
+    pub fn new_keyed<C, I>(items: I) -> BTreeMap<C, Self>
+    where
+        C: Ord,
+        I: IntoIterator<Item = (C, Item<K, V>)>,
+    {
+        let mut grouped = BTreeMap::<C, Vec<Item<K, V>>>::new();
+
+        for (key, item) in items {
+            grouped.entry(key).or_default().push(item);
+        }
+
+        grouped
+            .into_iter()
+            .map(|(key, items)| (key, Self::new(items)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trees_per_contig() {
+        let trees = ITree::<_, _>::new_keyed([
+            ("chr2", (5..10, 'a')),
+            ("chr1", (0..5, 'b')),
+            ("chr2", (0..5, 'c')),
+        ]);
+
+        assert_eq!(trees.keys().collect::<Vec<_>>(), [&"chr1", &"chr2"]);
+        assert_eq!(trees["chr1"], ITree::<_, _>::new([(0..5, 'b')]));
+        assert_eq!(
+            trees["chr2"],
+            ITree::<_, _>::new([(0..5, 'c'), (5..10, 'a')])
+        );
+    }
+}
@@ -1,29 +1,263 @@
-#![forbid(unsafe_code)]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+#![cfg_attr(
+    not(any(
+        feature = "unsafe-opt",
+        feature = "bytemuck",
+        feature = "mmap",
+        feature = "ffi",
+        feature = "prefetch",
+        feature = "simd"
+    )),
+    forbid(unsafe_code)
+)]
+#![cfg_attr(
+    any(
+        feature = "unsafe-opt",
+        feature = "bytemuck",
+        feature = "mmap",
+        feature = "ffi",
+        feature = "prefetch",
+        feature = "simd"
+    ),
+    deny(unsafe_code)
+)]
 #![deny(missing_docs, missing_debug_implementations)]
 
 //! A simple library implementing an immutable, flat representation of an [augmented interval tree](https://en.wikipedia.org/wiki/Interval_tree#Augmented_tree)
 //!
 //! Supports querying for overlapping intervals without temporary allocations and uses a flat memory layout that can be backed by memory maps.
+//!
+//! The crate is `no_std` compatible if the default `std` feature is disabled, requiring only the `alloc` crate for construction, querying and iteration.
+
+extern crate alloc;
 
+mod aligned;
+#[cfg(feature = "allocator-api2")]
+mod allocator;
+#[cfg(feature = "rkyv")]
+mod archive;
+#[cfg(feature = "bed")]
+mod bed;
+mod branchless;
+mod builder;
+#[cfg(feature = "bytemuck")]
+mod bytes;
+#[cfg(feature = "std")]
+mod calibrate;
+mod checked;
+mod codec;
+#[cfg(feature = "compact")]
+mod compact;
+mod compressed;
+mod coverage;
+mod dynamic;
+mod endian;
+mod ends;
+#[cfg(feature = "external")]
+mod external;
+mod eytzinger;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod fixed;
+#[cfg(any(feature = "std", feature = "bytemuck"))]
+mod format;
+mod indexed;
+#[cfg(feature = "serde")]
+pub mod items;
+mod join;
+mod key;
+mod keyed;
+mod map;
+mod merge;
+#[cfg(feature = "mmap")]
+mod mmap;
+#[cfg(feature = "testutil")]
+mod naive;
+mod offset;
+mod one_based;
+mod order;
+#[cfg(all(feature = "prefetch", target_arch = "x86_64"))]
+mod prefetch;
 mod query;
+mod rebuild;
+#[cfg(feature = "std")]
+mod rebuilding;
+#[cfg(feature = "serde")]
+mod serialization;
+mod set;
+mod shards;
+#[cfg(feature = "arc-swap")]
+mod shared;
+#[cfg(feature = "simd")]
+mod simd;
+mod size;
+#[cfg(feature = "smallvec")]
+mod small;
+mod soa;
 mod sort;
+mod storage;
+#[cfg(feature = "proptest")]
+pub mod strategy;
+mod validate;
+#[cfg(feature = "std")]
+mod writer;
+
+use alloc::boxed::Box;
+use core::hash::{Hash, Hasher};
+use core::iter::Map;
+use core::marker::PhantomData;
+use core::ops::{Deref, Range};
 
-use std::marker::PhantomData;
-use std::ops::{Deref, Range};
+pub use aligned::AlignedITree;
+#[cfg(feature = "allocator-api2")]
+pub use allocator::ITreeIn;
+#[cfg(feature = "bed")]
+pub use bed::{BedRecord, Strand};
+pub use builder::ITreeBuilder;
+#[cfg(feature = "bytemuck")]
+pub use bytes::{AlignedBytes, InvalidBytes};
+pub use codec::Codec;
+#[cfg(feature = "compact")]
+pub use compact::InvalidEncoding;
+pub use compressed::{CompressedITree, OffsetOverflow};
+pub use dynamic::DynamicITree;
+pub use endian::LittleEndian;
+pub use ends::EndOrderedITree;
+#[cfg(feature = "external")]
+pub use external::ExternalITreeBuilder;
+pub use eytzinger::EytzingerITree;
+#[cfg(feature = "heapless")]
+pub use fixed::CapacityExceeded;
+#[cfg(any(feature = "std", feature = "bytemuck"))]
+pub use format::FormatHeader;
+#[cfg(feature = "bytemuck")]
+pub use format::ZeroCopy;
+pub use indexed::{ITree32, IndexedITree};
+pub use key::PrimitiveKey;
+#[cfg(feature = "mmap")]
+pub use mmap::MmapNodes;
+#[cfg(feature = "testutil")]
+pub use naive::NaiveIntervals;
+pub use offset::MaxOffsetITree;
+pub use one_based::ZeroPosition;
+pub use order::{KeyOrder, OrderedBy};
+pub use query::Entry;
+#[cfg(feature = "std")]
+pub use rebuilding::RebuildingITree;
+#[cfg(feature = "serde")]
+pub use serialization::CowStr;
+pub use set::ITreeSet;
+pub use shards::ITreeShards;
+#[cfg(feature = "arc-swap")]
+pub use shared::SharedITree;
+#[cfg(feature = "simd")]
+pub use simd::SimdKey;
+pub use size::HeapSize;
+#[cfg(feature = "smallvec")]
+pub use small::SmallITree;
+pub use soa::ITreeSoA;
+pub use sort::EmptyInterval;
+pub use validate::InvalidTree;
+#[cfg(feature = "std")]
+pub use writer::ITreeWriter;
 
+#[cfg(feature = "bytemuck")]
+use bytemuck::AnyBitPattern;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// The items stored in the tree consisting of an interval and an associated value
 pub type Item<K, V> = (Range<K>, V);
 
-/// The nodes of which the tree is built consisting of an item and the maximum of the interval upper bounds in the subtree
-pub type Node<K, V> = (Item<K, V>, K);
+/// The nodes of which the tree is built consisting of an interval, the maximum of the interval upper bounds in the subtree and an associated value
+///
+/// The layout is `#[repr(C)]` with the fields in declaration order so that it is stable across compilations given stable layouts of `K` and `V`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "bytemuck", derive(AnyBitPattern))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct Node<K, V> {
+    /// The inclusive lower bound of the interval
+    pub start: K,
+    /// The exclusive upper bound of the interval
+    pub end: K,
+    /// The maximum of the interval upper bounds in the subtree rooted at this node
+    pub max: K,
+    /// The value associated with the interval
+    pub value: V,
+}
+
+impl<K, V> Node<K, V> {
+    /// The inclusive lower bound of the interval
+    pub fn start(&self) -> &K {
+        &self.start
+    }
+
+    /// The exclusive upper bound of the interval
+    pub fn end(&self) -> &K {
+        &self.end
+    }
+
+    /// The interval as a range of references to its bounds
+    pub fn interval(&self) -> Range<&K> {
+        &self.start..&self.end
+    }
+
+    /// The maximum of the interval upper bounds in the subtree rooted at this node
+    pub fn max(&self) -> &K {
+        &self.max
+    }
+
+    /// The value associated with the interval
+    pub fn value(&self) -> &V {
+        &self.value
+    }
+
+    /// Converts the node into the item it stores, dropping the maximum
+    pub fn into_item(self) -> Item<K, V> {
+        (self.start..self.end, self.value)
+    }
+}
+
+/// Compares the interval and value of the node with those of the given item, ignoring the maximum
+impl<K, V> PartialEq<Item<K, V>> for Node<K, V>
+where
+    K: PartialEq,
+    V: PartialEq,
+{
+    fn eq(&self, (interval, value): &Item<K, V>) -> bool {
+        self.start == interval.start && self.end == interval.end && self.value == *value
+    }
+}
+
+/// The nodes as they were represented before [`Node`] became a named struct
+#[deprecated(note = "use `Node` and its named fields instead")]
+pub type TupleNode<K, V> = (Item<K, V>, K);
+
+impl<K, V> From<(Item<K, V>, K)> for Node<K, V> {
+    fn from(((interval, value), max): (Item<K, V>, K)) -> Self {
+        Self {
+            start: interval.start,
+            end: interval.end,
+            max,
+            value,
+        }
+    }
+}
+
+impl<K, V> From<Node<K, V>> for (Item<K, V>, K) {
+    fn from(node: Node<K, V>) -> Self {
+        ((node.start..node.end, node.value), node.max)
+    }
+}
 
 /// Interval tree mapping half-open intervals with boundaries of type `K` to values of type `V`
 #[derive(Debug, Default, Clone)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[cfg_attr(feature = "serde", serde(transparent))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize))]
 pub struct ITree<K, V, S = Box<[Node<K, V>]>> {
     nodes: S,
     _marker: PhantomData<(K, V)>,
@@ -49,22 +283,144 @@ where
     }
 }
 
+/// Compares the sequence of items independently of the storage, ignoring the maxima
+impl<K, V, S, T> PartialEq<ITree<K, V, T>> for ITree<K, V, S>
+where
+    K: PartialEq,
+    V: PartialEq,
+    S: AsRef<[Node<K, V>]>,
+    T: AsRef<[Node<K, V>]>,
+{
+    fn eq(&self, other: &ITree<K, V, T>) -> bool {
+        let lhs = self.nodes.as_ref();
+        let rhs = other.nodes.as_ref();
+
+        lhs.len() == rhs.len()
+            && lhs.iter().zip(rhs).all(|(lhs, rhs)| {
+                lhs.start == rhs.start && lhs.end == rhs.end && lhs.value == rhs.value
+            })
+    }
+}
+
+impl<K, V, S> Eq for ITree<K, V, S>
+where
+    K: Eq,
+    V: Eq,
+    S: AsRef<[Node<K, V>]>,
+{
+}
+
+/// Hashes the sequence of items consistently with [`PartialEq`]
+impl<K, V, S> Hash for ITree<K, V, S>
+where
+    K: Hash,
+    V: Hash,
+    S: AsRef<[Node<K, V>]>,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let nodes = self.nodes.as_ref();
+
+        state.write_usize(nodes.len());
+
+        for node in nodes {
+            node.start.hash(state);
+            node.end.hash(state);
+            node.value.hash(state);
+        }
+    }
+}
+
+/// Consumes the tree yielding the items in the order of their interval starts
+impl<K, V, S> IntoIterator for ITree<K, V, S>
+where
+    S: IntoIterator<Item = Node<K, V>>,
+{
+    type Item = Item<K, V>;
+    type IntoIter = Map<S::IntoIter, fn(Node<K, V>) -> Item<K, V>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.nodes.into_iter().map(Node::into_item)
+    }
+}
+
+/// Mutable access to the nodes
+///
+/// Modifying the nodes can break the invariants of the tree which is safe but will lead to incorrect results unless [`rebuild_in_place`][ITree::rebuild_in_place] is called afterwards.
+impl<K, V, S> AsMut<[Node<K, V>]> for ITree<K, V, S>
+where
+    S: AsMut<[Node<K, V>]>,
+{
+    fn as_mut(&mut self) -> &mut [Node<K, V>] {
+        self.nodes.as_mut()
+    }
+}
+
 impl<K, V, S> ITree<K, V, S>
 where
     S: AsRef<[Node<K, V>]>,
 {
+    /// Iterate over all nodes in the order of their interval starts
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &Node<K, V>> + ExactSizeIterator {
+        self.nodes.as_ref().iter()
+    }
+
+    /// Iterate over all nodes in the reverse order of their interval starts
+    pub fn iter_rev(&self) -> impl DoubleEndedIterator<Item = &Node<K, V>> + ExactSizeIterator {
+        self.nodes.as_ref().iter().rev()
+    }
+
+    /// The nodes in the order of their interval starts, for building custom traversals
+    ///
+    /// The nodes form an implicit binary tree: The root of any subslice is found at half its length and its left and right subtrees are the subslices before and after it. The [`max`][Node::max] of each node is the maximum of the interval upper bounds of all nodes in its subtree, so a subtree can be skipped if its root's maximum does not exceed the start of the query.
+    pub fn nodes(&self) -> &[Node<K, V>] {
+        self.nodes.as_ref()
+    }
+
+    /// Iterate over the maxima of the interval upper bounds stored in the nodes, in the order of the [`nodes`][Self::nodes]
+    pub fn max_ends(&self) -> impl DoubleEndedIterator<Item = &K> + ExactSizeIterator {
+        self.nodes.as_ref().iter().map(|node| &node.max)
+    }
+
+    /// The item at the given position in the order of interval starts
+    ///
+    /// As the nodes store the bounds of an interval separately instead of an [`Item`], this yields an [`Entry`] borrowing them and no `Index<usize>` implementation yielding `&Item` can exist. Indexing the tree directly yields the [`Node`] including its maximum instead.
+    pub fn get(&self, index: usize) -> Option<Entry<'_, K, V>> {
+        self.nodes.as_ref().get(index).map(Entry::from)
+    }
+}
+
+impl<K, V, S> ITree<K, V, S> {
     /// Interprets the given `nodes` as a tree
     ///
-    /// Supplying `nodes` which are not actually organized as an interval tree is safe but will lead to incorrect results.
-    pub fn new_unchecked(nodes: S) -> Self {
+    /// Supplying `nodes` which are not actually organized as an interval tree is safe but will lead to incorrect results. Use [`from_nodes`][Self::from_nodes] or [`check`][Self::check] to verify them, or [`new_debug_checked`][Self::new_debug_checked] to verify them in debug builds only.
+    ///
+    /// This is a `const fn` so that e.g. a `static` can be initialized using borrowed nodes. For primitive keys, [`is_valid_const`][Self::is_valid_const] can verify them in a const assertion.
+    pub const fn new_unchecked(nodes: S) -> Self {
         Self {
             nodes,
             _marker: PhantomData,
         }
     }
+}
+
+impl<K, V, S> ITree<K, V, S>
+where
+    S: AsMut<[Node<K, V>]>,
+{
+    /// Iterate over all intervals with mutable access to their values
+    ///
+    /// The intervals themselves cannot be modified so that the tree invariants are preserved.
+    pub fn iter_mut(
+        &mut self,
+    ) -> impl DoubleEndedIterator<Item = (Range<&K>, &mut V)> + ExactSizeIterator {
+        self.nodes
+            .as_mut()
+            .iter_mut()
+            .map(|node| (&node.start..&node.end, &mut node.value))
+    }
 
-    /// Iterate over all intervals
-    pub fn iter(&self) -> impl ExactSizeIterator<Item = &Item<K, V>> {
-        self.nodes.as_ref().iter().map(|node| &node.0)
+    /// Iterate over all values mutably
+    pub fn values_mut(&mut self) -> impl DoubleEndedIterator<Item = &mut V> + ExactSizeIterator {
+        self.nodes.as_mut().iter_mut().map(|node| &mut node.value)
     }
 }
@@ -0,0 +1,98 @@
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use crate::{ITree, Node};
+
+impl<K, V, S> ITree<K, V, S>
+where
+    S: Into<Vec<Node<K, V>>>,
+{
+    /// Transform the values of all items producing a new tree
+    ///
+    /// Re-uses the ordering of the items and the maximum of the interval upper bounds so nothing needs to be sorted.
+    pub fn map_values<W, F, T>(self, mut f: F) -> ITree<K, W, T>
+    where
+        F: FnMut(V) -> W,
+        T: FromIterator<Node<K, W>>,
+    {
+        let nodes = self
+            .nodes
+            .into()
+            .into_iter()
+            .map(|node| Node {
+                start: node.start,
+                end: node.end,
+                max: node.max,
+                value: f(node.value),
+            })
+            .collect();
+
+        ITree {
+            nodes,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Transform the interval bounds of all items producing a new tree
+    ///
+    /// The mapping `f` must be strictly increasing, e.g. shifting or scaling by a positive factor, so that the ordering of the items and the maximum of the interval upper bounds can be re-used. Supplying other mappings is safe but will lead to incorrect results.
+    pub fn map_keys_monotonic<L, F, T>(self, f: F) -> ITree<L, V, T>
+    where
+        F: Fn(K) -> L,
+        T: FromIterator<Node<L, V>>,
+    {
+        let nodes = self
+            .nodes
+            .into()
+            .into_iter()
+            .map(|node| Node {
+                start: f(node.start),
+                end: f(node.end),
+                max: f(node.max),
+                value: node.value,
+            })
+            .collect();
+
+        ITree {
+            nodes,
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn into_iter_yields_owned_items() {
+        let tree = ITree::<_, _>::new([(2..3, 2), (0..5, 0), (1..2, 1)]);
+
+        let items = tree.into_iter().collect::<Vec<_>>();
+        assert_eq!(items, [(0..5, 0), (1..2, 1), (2..3, 2)]);
+    }
+
+    #[test]
+    fn map_values_keeps_layout() {
+        let tree = ITree::<_, _>::new([(2..3, 2), (0..5, 0), (1..2, 1)]);
+
+        let tree: ITree<_, _, Vec<_>> = tree.map_values(|value| value.to_string());
+        assert!(tree.is_valid());
+        assert!(tree.iter().eq(&[
+            (0..5, "0".to_owned()),
+            (1..2, "1".to_owned()),
+            (2..3, "2".to_owned())
+        ]));
+    }
+
+    #[test]
+    fn map_keys_monotonic_keeps_layout() {
+        let tree = ITree::<_, _>::new([(2..3, 2), (0..5, 0), (1..2, 1)]);
+
+        let tree: ITree<_, _> = tree.map_keys_monotonic(|key| i64::from(key) * 1000 - 1);
+        assert!(tree.is_valid());
+        assert!(tree
+            .iter()
+            .eq(&[(-1..4999, 0), (999..1999, 1), (1999..2999, 2)]));
+    }
+}
@@ -0,0 +1,153 @@
+use alloc::collections::BinaryHeap;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use crate::sort::cmp_intervals;
+use crate::{ITree, Item, Node};
+
+impl<K, V, S> ITree<K, V, S>
+where
+    K: Ord + Clone,
+    S: AsMut<[Node<K, V>]> + FromIterator<Node<K, V>>,
+{
+    /// Construct a new tree by merging the given `runs` of items which are each already sorted by interval start
+    ///
+    /// Supplying `runs` which are not sorted is safe but will lead to incorrect results. This is checked only if debug assertions are enabled.
+    pub fn from_sorted_runs<R, I>(runs: R) -> Self
+    where
+        R: IntoIterator<Item = I>,
+        I: IntoIterator<Item = Item<K, V>>,
+    {
+        Self::from_sorted(Merge::new(runs))
+    }
+}
+
+struct Merge<K, V, I> {
+    runs: Vec<I>,
+    heads: BinaryHeap<Head<K, V>>,
+}
+
+impl<K, V, I> Merge<K, V, I>
+where
+    K: Ord,
+    I: Iterator<Item = Item<K, V>>,
+{
+    fn new<R>(runs: R) -> Self
+    where
+        R: IntoIterator,
+        R::Item: IntoIterator<IntoIter = I>,
+    {
+        let mut runs = runs
+            .into_iter()
+            .map(IntoIterator::into_iter)
+            .collect::<Vec<_>>();
+
+        let heads = runs
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(run, items)| Some(Head::new(items.next()?, run)))
+            .collect();
+
+        Self { runs, heads }
+    }
+}
+
+impl<K, V, I> Iterator for Merge<K, V, I>
+where
+    K: Ord,
+    I: Iterator<Item = Item<K, V>>,
+{
+    type Item = Item<K, V>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let head = self.heads.pop()?;
+
+        if let Some(item) = self.runs[head.run].next() {
+            self.heads.push(Head::new(item, head.run));
+        }
+
+        Some(head.item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.runs.iter().fold(
+            (self.heads.len(), Some(self.heads.len())),
+            |(lower, upper), items| {
+                let (items_lower, items_upper) = items.size_hint();
+
+                (
+                    lower.saturating_add(items_lower),
+                    upper
+                        .zip(items_upper)
+                        .and_then(|(lhs, rhs)| lhs.checked_add(rhs)),
+                )
+            },
+        )
+    }
+}
+
+pub(crate) struct Head<K, V> {
+    pub(crate) item: Item<K, V>,
+    pub(crate) run: usize,
+}
+
+impl<K, V> Head<K, V> {
+    pub(crate) fn new(item: Item<K, V>, run: usize) -> Self {
+        Self { item, run }
+    }
+}
+
+impl<K, V> PartialEq for Head<K, V>
+where
+    K: Ord,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl<K, V> Eq for Head<K, V> where K: Ord {}
+
+impl<K, V> PartialOrd for Head<K, V>
+where
+    K: Ord,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K, V> Ord for Head<K, V>
+where
+    K: Ord,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed as `BinaryHeap` is a max-heap.
+        cmp_intervals(&other.item.0, &self.item.0).then_with(|| other.run.cmp(&self.run))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_sorted_runs() {
+        let runs = vec![
+            vec![(0..3, 0), (4..5, 1), (8..9, 2)],
+            vec![],
+            vec![(0..5, 3), (4..5, 4), (6..7, 5)],
+        ];
+
+        let tree = ITree::<_, _>::from_sorted_runs(runs);
+        assert!(tree.is_valid());
+        assert!(tree.iter().eq(&[
+            (0..5, 3),
+            (0..3, 0),
+            (4..5, 1),
+            (4..5, 4),
+            (6..7, 5),
+            (8..9, 2)
+        ]));
+    }
+}
@@ -0,0 +1,115 @@
+use std::fmt;
+use std::fs::File;
+use std::io;
+use std::marker::PhantomData;
+use std::mem::size_of;
+use std::path::Path;
+
+use bytemuck::cast_slice;
+use memmap2::Mmap;
+
+use crate::format::HEADER_LEN;
+use crate::{ITree, Node, ZeroCopy};
+
+/// Storage of nodes backed by a read-only memory map of a file in the binary format
+///
+/// Requires the `mmap` feature and is obtained via [`open_mmap`][ITree::open_mmap].
+pub struct MmapNodes<K, V> {
+    mmap: Mmap,
+    len: usize,
+    _marker: PhantomData<(K, V)>,
+}
+
+impl<K, V> ITree<K, V, MmapNodes<K, V>>
+where
+    K: Ord + ZeroCopy,
+    V: ZeroCopy,
+{
+    /// Memory map a file written using [`write_to`][ITree::write_to] and use it to back a tree
+    ///
+    /// Requires the `mmap` feature. The header, the alignment and length of the nodes, the checksum and the invariants of the tree are verified as by [`from_bytes`][ITree::from_bytes] before the tree is returned, failing with [`InvalidData`][io::ErrorKind::InvalidData] otherwise.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified, e.g. truncated, while the tree exists as this is undefined behaviour for memory maps.
+    #[allow(unsafe_code)]
+    pub unsafe fn open_mmap<P>(path: P) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let file = File::open(path)?;
+
+        // SAFETY: The caller guarantees that the file is not modified while the map exists.
+        let mmap = unsafe { Mmap::map(&file)? };
+
+        let len = ITree::<K, V, &[Node<K, V>]>::from_bytes(&mmap)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?
+            .len();
+
+        Ok(Self {
+            nodes: MmapNodes {
+                mmap,
+                len,
+                _marker: PhantomData,
+            },
+            _marker: PhantomData,
+        })
+    }
+}
+
+impl<K, V> AsRef<[Node<K, V>]> for MmapNodes<K, V>
+where
+    K: ZeroCopy,
+    V: ZeroCopy,
+{
+    fn as_ref(&self) -> &[Node<K, V>] {
+        cast_slice(&self.mmap[HEADER_LEN..HEADER_LEN + self.len * size_of::<Node<K, V>>()])
+    }
+}
+
+impl<K, V> fmt::Debug for MmapNodes<K, V> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("MmapNodes")
+            .field("len", &self.len)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs::{remove_file, write};
+    use std::ops::ControlFlow;
+    use std::process::id;
+
+    #[test]
+    #[allow(unsafe_code)]
+    fn open_mmapped_tree() {
+        let path = std::env::temp_dir().join(format!("sif-itree-mmap-{}", id()));
+
+        let tree = ITree::<u32, u32>::new([(0..5, 0), (1..2, 1), (2..3, 2)]);
+        tree.write_to(File::create(&path).unwrap()).unwrap();
+
+        // SAFETY: The file is only modified after the tree is dropped.
+        let mmapped = unsafe { ITree::<u32, u32, MmapNodes<_, _>>::open_mmap(&path).unwrap() };
+        assert_eq!(mmapped, tree);
+
+        let mut result = Vec::new();
+        let _ = mmapped.query(1..3, |entry| {
+            result.push(*entry.value());
+            ControlFlow::Continue(())
+        });
+        result.sort_unstable();
+        assert_eq!(result, [0, 1, 2]);
+
+        drop(mmapped);
+
+        write(&path, b"SIFITREE").unwrap();
+        // SAFETY: The file is not modified again.
+        let err = unsafe { ITree::<u32, u32, MmapNodes<_, _>>::open_mmap(&path).unwrap_err() };
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,184 @@
+use alloc::vec::Vec;
+use core::ops::{ControlFlow, Range};
+
+use crate::{Entry, Item};
+
+/// Reference implementation answering queries by scanning all items, for differential testing of trees
+///
+/// Requires the `testutil` feature. Each method mirrors the method of [`ITree`][crate::ITree] of the same name, but yields items in the order they were given instead of the order of the tree.
+#[derive(Debug, Default, Clone)]
+pub struct NaiveIntervals<K, V> {
+    items: Vec<Item<K, V>>,
+}
+
+impl<K, V> NaiveIntervals<K, V> {
+    /// Store the given `items` in their current order
+    pub fn new<I>(items: I) -> Self
+    where
+        I: IntoIterator<Item = Item<K, V>>,
+    {
+        Self {
+            items: items.into_iter().collect(),
+        }
+    }
+
+    /// The items in the order they were given
+    pub fn items(&self) -> &[Item<K, V>] {
+        &self.items
+    }
+
+    /// Query for all intervals overlapping the given interval
+    pub fn query<'a, H>(&'a self, interval: Range<K>, mut handler: H) -> ControlFlow<()>
+    where
+        K: Ord,
+        H: FnMut(Entry<'a, K, V>) -> ControlFlow<()>,
+    {
+        for (item, value) in &self.items {
+            if overlaps(item, &interval) {
+                handler(Entry::new(&item.start, &item.end, value))?;
+            }
+        }
+
+        ControlFlow::Continue(())
+    }
+
+    /// Find all pairs of overlapping query intervals and items, returned as parallel arrays of indices into the `queries` and the [`items`][Self::items]
+    pub fn overlap_join<I>(&self, queries: I) -> (Vec<usize>, Vec<usize>)
+    where
+        K: Ord,
+        I: IntoIterator<Item = Range<K>>,
+    {
+        let mut query_indices = Vec::new();
+        let mut item_indices = Vec::new();
+
+        for (query_index, interval) in queries.into_iter().enumerate() {
+            for (item_index, (item, _value)) in self.items.iter().enumerate() {
+                if overlaps(item, &interval) {
+                    query_indices.push(query_index);
+                    item_indices.push(item_index);
+                }
+            }
+        }
+
+        (query_indices, item_indices)
+    }
+
+    /// Compute the depth profile as disjoint ranges annotated with the number of overlapping intervals
+    pub fn coverage(&self) -> Vec<(Range<K>, usize)>
+    where
+        K: Ord + Clone,
+    {
+        let mut points = self
+            .items
+            .iter()
+            .filter(|(item, _value)| item.start < item.end)
+            .flat_map(|(item, _value)| [&item.start, &item.end])
+            .collect::<Vec<_>>();
+        points.sort_unstable();
+        points.dedup();
+
+        let mut ranges = Vec::<(Range<K>, usize)>::new();
+
+        for pair in points.windows(2) {
+            let depth = self
+                .items
+                .iter()
+                .filter(|(item, _value)| item.start <= *pair[0] && *pair[1] <= item.end)
+                .count();
+
+            if depth == 0 {
+                continue;
+            }
+
+            match ranges.last_mut() {
+                Some((range, last_depth)) if range.end == *pair[0] && *last_depth == depth => {
+                    range.end = pair[1].clone();
+                }
+                _ => ranges.push((pair[0].clone()..pair[1].clone(), depth)),
+            }
+        }
+
+        ranges
+    }
+}
+
+fn overlaps<K>(item: &Range<K>, interval: &Range<K>) -> bool
+where
+    K: Ord,
+{
+    item.start < interval.end && interval.start < item.end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use proptest::{collection::vec, test_runner::TestRunner};
+
+    use crate::ITree;
+
+    #[test]
+    fn tree_matches_oracle() {
+        TestRunner::default()
+            .run(
+                &(
+                    vec((0_i32..100, 0_i32..10), 0..100),
+                    vec((0_i32..100, 0_i32..10), 0..10),
+                ),
+                |(items, queries)| {
+                    let items = items
+                        .iter()
+                        .enumerate()
+                        .map(|(idx, &(start, len))| (start..start + len, idx))
+                        .collect::<Vec<_>>();
+
+                    let queries = queries
+                        .iter()
+                        .map(|&(start, len)| start..start + len)
+                        .collect::<Vec<_>>();
+
+                    let tree = ITree::<_, _>::new(items.iter().cloned());
+                    let naive = NaiveIntervals::new(items);
+
+                    for query in &queries {
+                        let mut expected = Vec::new();
+                        let _ = naive.query(query.clone(), |entry| {
+                            expected.push(*entry.value());
+                            ControlFlow::Continue(())
+                        });
+
+                        let mut actual = Vec::new();
+                        let _ = tree.query(query.clone(), |entry| {
+                            actual.push(*entry.value());
+                            ControlFlow::Continue(())
+                        });
+
+                        expected.sort_unstable();
+                        actual.sort_unstable();
+                        assert_eq!(expected, actual);
+                    }
+
+                    let (query_indices, item_indices) = naive.overlap_join(queries.iter().cloned());
+                    let mut expected = query_indices
+                        .into_iter()
+                        .zip(item_indices.into_iter().map(|idx| naive.items()[idx].1))
+                        .collect::<Vec<_>>();
+
+                    let (query_indices, node_indices) = tree.overlap_join(queries.iter().cloned());
+                    let mut actual = query_indices
+                        .into_iter()
+                        .zip(node_indices.into_iter().map(|idx| tree[idx].value))
+                        .collect::<Vec<_>>();
+
+                    expected.sort_unstable();
+                    actual.sort_unstable();
+                    assert_eq!(expected, actual);
+
+                    assert_eq!(naive.coverage(), tree.coverage());
+
+                    Ok(())
+                },
+            )
+            .unwrap();
+    }
+}
@@ -0,0 +1,263 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::ops::{ControlFlow, Range};
+
+#[cfg(feature = "serde")]
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize};
+
+use crate::query::{split, traverse, Subtree};
+#[cfg(feature = "serde")]
+use crate::InvalidTree;
+use crate::{Entry, ITree, Node, OffsetOverflow, PrimitiveKey};
+
+/// Interval tree storing the maximum of the interval upper bounds in each subtree as a `u32` offset from the end of its root
+///
+/// This shrinks the nodes for keys wider than 32 bits, e.g. by eight bytes for `u64` timestamps.
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct MaxOffsetITree<K, V> {
+    nodes: Box<[OffsetNode<K, V>]>,
+}
+
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+struct OffsetNode<K, V> {
+    start: K,
+    end: K,
+    max: u32,
+    value: V,
+}
+
+impl<K, V> MaxOffsetITree<K, V>
+where
+    K: PrimitiveKey,
+{
+    /// The number of items in the tree
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Whether the tree contains no items
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Iterate over all items in the order of their interval starts
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = Entry<'_, K, V>> + ExactSizeIterator {
+        self.nodes
+            .iter()
+            .map(|node| Entry::new(&node.start, &node.end, &node.value))
+    }
+
+    /// Query for all intervals overlapping the given interval
+    pub fn query<'a, H>(&'a self, interval: Range<K>, mut handler: H) -> ControlFlow<()>
+    where
+        H: FnMut(Entry<'a, K, V>) -> ControlFlow<()>,
+    {
+        traverse(&interval, &*self.nodes, &mut |node| {
+            handler(Entry::new(&node.start, &node.end, &node.value))
+        })
+    }
+}
+
+/// Stores the maxima of the given tree as offsets, failing if an offset does not fit into 32 bits
+impl<K, V, S> TryFrom<ITree<K, V, S>> for MaxOffsetITree<K, V>
+where
+    K: PrimitiveKey,
+    S: Into<Vec<Node<K, V>>>,
+{
+    type Error = OffsetOverflow;
+
+    fn try_from(tree: ITree<K, V, S>) -> Result<Self, Self::Error> {
+        let nodes: Vec<_> = tree.nodes.into();
+
+        let nodes = nodes
+            .into_iter()
+            .enumerate()
+            .map(|(index, node)| {
+                Ok(OffsetNode {
+                    max: node
+                        .max
+                        .offset_from(node.end)
+                        .ok_or(OffsetOverflow(index))?,
+                    start: node.start,
+                    end: node.end,
+                    value: node.value,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { nodes })
+    }
+}
+
+/// Rejects maxima which overflow when decoded and [checks][ITree::check] that the decoded nodes are organized as an interval tree
+#[cfg(feature = "serde")]
+impl<'de, K, V> Deserialize<'de> for MaxOffsetITree<K, V>
+where
+    K: PrimitiveKey + Deserialize<'de>,
+    V: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let Parts { nodes } = Parts::<K, V>::deserialize(deserializer)?;
+
+        let decoded = nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| {
+                let max = K::from_i128(node.end.to_i128() + i128::from(node.max))
+                    .ok_or(InvalidTree::InvalidMax(index))?;
+
+                Ok(Node {
+                    start: node.start,
+                    end: node.end,
+                    max,
+                    value: (),
+                })
+            })
+            .collect::<Result<Vec<_>, InvalidTree>>()
+            .map_err(D::Error::custom)?;
+
+        crate::validate::validate(&decoded).map_err(D::Error::custom)?;
+
+        Ok(Self { nodes })
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+#[serde(rename = "MaxOffsetITree")]
+struct Parts<K, V> {
+    nodes: Box<[OffsetNode<K, V>]>,
+}
+
+impl<'a, K, V> Subtree for &'a [OffsetNode<K, V>]
+where
+    K: PrimitiveKey,
+{
+    type Key = K;
+    type Bound = K;
+    type Root = &'a OffsetNode<K, V>;
+
+    fn len(self) -> usize {
+        <[_]>::len(self)
+    }
+
+    fn split(self) -> Option<(Self, Self::Root, Self)> {
+        split(self)
+    }
+
+    fn start(root: Self::Root) -> Self::Bound {
+        root.start
+    }
+
+    fn end(root: Self::Root) -> Self::Bound {
+        root.end
+    }
+
+    fn max(root: Self::Root) -> Self::Bound {
+        root.end.add_offset(root.max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::mem::size_of;
+
+    use proptest::{collection::vec, test_runner::TestRunner};
+
+    #[test]
+    fn nodes_are_smaller() {
+        assert_eq!(size_of::<OffsetNode<u64, u32>>(), 3 * size_of::<u64>());
+        assert_eq!(size_of::<Node<u64, u32>>(), 4 * size_of::<u64>());
+    }
+
+    #[test]
+    fn query_random() {
+        const DOM: Range<i64> = -1000..1000;
+        const LEN: usize = 1000_usize;
+
+        TestRunner::default()
+            .run(
+                &(vec(DOM, LEN), vec(DOM, LEN), DOM, DOM),
+                |(start, end, query_start, query_end)| {
+                    let tree = ITree::<_, _>::new(
+                        start
+                            .iter()
+                            .zip(&end)
+                            .enumerate()
+                            .map(|(index, (&start, &end))| (start..end, index)),
+                    );
+
+                    let mut result1 = Vec::new();
+                    let _ = tree.query(query_start..query_end, |entry| {
+                        result1.push(*entry.value());
+                        ControlFlow::Continue(())
+                    });
+
+                    let tree = MaxOffsetITree::try_from(tree).unwrap();
+
+                    let mut result2 = Vec::new();
+                    let _ = tree.query(query_start..query_end, |entry| {
+                        result2.push(*entry.value());
+                        ControlFlow::Continue(())
+                    });
+
+                    assert_eq!(result1, result2);
+
+                    Ok(())
+                },
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn reject_distant_maxima() {
+        let tree = ITree::<_, _>::new([(0_u64..1 << 40, ()), (1..2, ()), (2..3, ())]);
+
+        assert_eq!(
+            MaxOffsetITree::try_from(tree).unwrap_err(),
+            OffsetOverflow(1)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_rejects_invalid_trees() {
+        let tree =
+            MaxOffsetITree::try_from(ITree::<u8, _>::new([(0..5, 'a'), (1..2, 'b')])).unwrap();
+
+        let json = serde_json::to_string(&tree).unwrap();
+        let other = serde_json::from_str::<MaxOffsetITree<u8, char>>(&json).unwrap();
+        assert!(other
+            .iter()
+            .map(|entry| entry.interval())
+            .eq(tree.iter().map(|entry| entry.interval())));
+
+        let err = serde_json::from_str::<MaxOffsetITree<u8, char>>(&json.replacen(
+            r#""max":3"#,
+            r#""max":300"#,
+            1,
+        ))
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("node 1 has an invalid maximum end"));
+
+        let err = serde_json::from_str::<MaxOffsetITree<u8, char>>(&json.replacen(
+            r#""max":3"#,
+            r#""max":2"#,
+            1,
+        ))
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("node 1 has an invalid maximum end"));
+    }
+}
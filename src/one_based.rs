@@ -0,0 +1,140 @@
+use core::error::Error;
+use core::fmt;
+use core::ops::{ControlFlow, RangeInclusive};
+
+use crate::{Entry, ITree, Node};
+
+impl<V, S> ITree<u64, V, S>
+where
+    S: AsMut<[Node<u64, V>]> + FromIterator<Node<u64, V>>,
+{
+    /// Construct a new tree from items with 1-based, inclusive coordinates as used by GFF, VCF and SAM
+    ///
+    /// The intervals are stored using 0-based, half-open coordinates like those of BED files, i.e. `101..=200` becomes `100..200`.
+    ///
+    /// # Panics
+    ///
+    /// If any interval starts at position zero. Use [`try_new_one_based`][Self::try_new_one_based] for input which is not known to be valid.
+    pub fn new_one_based<I>(items: I) -> Self
+    where
+        I: IntoIterator<Item = (RangeInclusive<u64>, V)>,
+    {
+        match Self::try_new_one_based(items) {
+            Ok(tree) => tree,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Construct a new tree from items with 1-based, inclusive coordinates, failing if any interval starts at position zero
+    ///
+    /// Converts the intervals like [`new_one_based`][Self::new_one_based].
+    pub fn try_new_one_based<I>(items: I) -> Result<Self, ZeroPosition>
+    where
+        I: IntoIterator<Item = (RangeInclusive<u64>, V)>,
+    {
+        Self::try_new(
+            items
+                .into_iter()
+                .enumerate()
+                .map(|(index, (interval, value))| {
+                    let (start, end) = interval.into_inner();
+
+                    if start == 0 {
+                        return Err(ZeroPosition(index));
+                    }
+
+                    Ok((start - 1..end, value))
+                }),
+        )
+    }
+}
+
+impl<V, S> ITree<u64, V, S>
+where
+    S: AsRef<[Node<u64, V>]>,
+{
+    /// Query for all intervals overlapping the given interval in 1-based, inclusive coordinates, e.g. of a region like `chr1:101-200`
+    ///
+    /// The entries passed to the `handler` use the 0-based, half-open coordinates of the tree. As position zero does not exist in 1-based coordinates, an interval starting there is treated like one starting at position one.
+    pub fn query_one_based<'a, H>(
+        &'a self,
+        interval: RangeInclusive<u64>,
+        handler: H,
+    ) -> ControlFlow<()>
+    where
+        H: FnMut(Entry<'a, u64, V>) -> ControlFlow<()>,
+    {
+        let (start, end) = interval.into_inner();
+
+        self.query(start.saturating_sub(1)..end, handler)
+    }
+}
+
+/// Error indicating that the item at the given index of the input starts at position zero which does not exist in 1-based coordinates
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ZeroPosition(pub usize);
+
+impl fmt::Display for ZeroPosition {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "item {} starts at position zero", self.0)
+    }
+}
+
+impl Error for ZeroPosition {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn one_based_coordinates() {
+        let tree = ITree::<_, _>::new_one_based([(101..=200, 'a'), (201..=300, 'b'), (1..=1, 'c')]);
+        assert_eq!(
+            tree.iter()
+                .map(|node| node.start..node.end)
+                .collect::<Vec<_>>(),
+            [0..1, 100..200, 200..300]
+        );
+
+        let mut values = Vec::new();
+        let _ = tree.query_one_based(200..=200, |entry| {
+            values.push(*entry.value());
+            ControlFlow::Continue(())
+        });
+        assert_eq!(values, ['a']);
+
+        values.clear();
+        let _ = tree.query_one_based(1..=201, |entry| {
+            values.push(*entry.value());
+            ControlFlow::Continue(())
+        });
+        values.sort_unstable();
+        assert_eq!(values, ['a', 'b', 'c']);
+    }
+
+    #[test]
+    fn one_based_query_from_zero() {
+        let tree = ITree::<_, _>::new_one_based([(1..=10, ())]);
+
+        let mut count = 0;
+        let _ = tree.query_one_based(0..=10, |_entry| {
+            count += 1;
+            ControlFlow::Continue(())
+        });
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn try_one_based_rejects_zero() {
+        assert_eq!(
+            ITree::<_, _>::try_new_one_based([(1..=10, ()), (0..=10, ())]).unwrap_err(),
+            ZeroPosition(1)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "item 0 starts at position zero")]
+    fn one_based_rejects_zero() {
+        ITree::<_, _>::new_one_based([(0..=10, ())]);
+    }
+}
@@ -0,0 +1,132 @@
+use core::cmp::Ordering;
+use core::fmt;
+use core::marker::PhantomData;
+
+/// Defines an ordering of keys which do not implement a suitable [`Ord`]
+///
+/// Used via [`OrderedBy`] so that the same ordering is applied when constructing and when querying a tree.
+pub trait KeyOrder<K: ?Sized> {
+    /// Compare two keys
+    ///
+    /// Must define a total order just like [`Ord::cmp`].
+    fn cmp(lhs: &K, rhs: &K) -> Ordering;
+}
+
+/// A key which is ordered according to `O`
+///
+/// For example, `ITree<OrderedBy<String, CaseInsensitive>, V>` with a suitable definition of `CaseInsensitive` will compare interval bounds case-insensitively.
+#[repr(transparent)]
+pub struct OrderedBy<K, O> {
+    /// The wrapped key
+    pub key: K,
+    _marker: PhantomData<fn() -> O>,
+}
+
+impl<K, O> OrderedBy<K, O> {
+    /// Wrap the given `key`
+    pub const fn new(key: K) -> Self {
+        Self {
+            key,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Unwrap the key
+    pub fn into_inner(self) -> K {
+        self.key
+    }
+}
+
+impl<K, O> From<K> for OrderedBy<K, O> {
+    fn from(key: K) -> Self {
+        Self::new(key)
+    }
+}
+
+impl<K, O> Clone for OrderedBy<K, O>
+where
+    K: Clone,
+{
+    fn clone(&self) -> Self {
+        Self::new(self.key.clone())
+    }
+}
+
+impl<K, O> Copy for OrderedBy<K, O> where K: Copy {}
+
+impl<K, O> fmt::Debug for OrderedBy<K, O>
+where
+    K: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.key.fmt(fmt)
+    }
+}
+
+impl<K, O> PartialEq for OrderedBy<K, O>
+where
+    O: KeyOrder<K>,
+{
+    fn eq(&self, other: &Self) -> bool {
+        O::cmp(&self.key, &other.key).is_eq()
+    }
+}
+
+impl<K, O> Eq for OrderedBy<K, O> where O: KeyOrder<K> {}
+
+impl<K, O> PartialOrd for OrderedBy<K, O>
+where
+    O: KeyOrder<K>,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K, O> Ord for OrderedBy<K, O>
+where
+    O: KeyOrder<K>,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        O::cmp(&self.key, &other.key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::ops::ControlFlow;
+
+    use crate::ITree;
+
+    struct CaseInsensitive;
+
+    impl KeyOrder<&str> for CaseInsensitive {
+        fn cmp(lhs: &&str, rhs: &&str) -> Ordering {
+            lhs.bytes()
+                .map(|byte| byte.to_ascii_lowercase())
+                .cmp(rhs.bytes().map(|byte| byte.to_ascii_lowercase()))
+        }
+    }
+
+    #[test]
+    fn query_case_insensitive() {
+        type Key = OrderedBy<&'static str, CaseInsensitive>;
+
+        let tree = ITree::<Key, _>::new([
+            (Key::new("a")..Key::new("C"), 0),
+            (Key::new("B")..Key::new("d"), 1),
+            (Key::new("x")..Key::new("Z"), 2),
+        ]);
+        assert!(tree.is_valid());
+
+        let mut result = Vec::new();
+        let _ = tree.query(Key::new("c")..Key::new("Y"), |entry| {
+            result.push(*entry.value());
+            ControlFlow::Continue(())
+        });
+        result.sort_unstable();
+        assert_eq!(result, [1, 2]);
+    }
+}
@@ -0,0 +1,122 @@
+use core::arch::x86_64::{_mm_prefetch, _MM_HINT_T0};
+use core::ops::{ControlFlow, Range};
+
+use crate::query::{split, traverse, Subtree};
+use crate::{Entry, ITree, Node};
+
+impl<K, V, S> ITree<K, V, S>
+where
+    K: Copy + Ord,
+    S: AsRef<[Node<K, V>]>,
+{
+    /// Query for all intervals overlapping the given interval, prefetching the midpoints of both children before deciding which to descend into
+    ///
+    /// Requires the `prefetch` feature and is only available on x86-64. As the prefetches of both children are issued before the branch is resolved, their memory latency overlaps which helps if the tree does not fit into the cache. Yields the same items as [`query`][Self::query].
+    pub fn query_prefetch<'a, H>(&'a self, interval: Range<K>, mut handler: H) -> ControlFlow<()>
+    where
+        H: FnMut(Entry<'a, K, V>) -> ControlFlow<()>,
+    {
+        traverse(&interval, Prefetching(self.nodes.as_ref()), &mut |node| {
+            handler(node.into())
+        })
+    }
+}
+
+fn prefetch<K, V>(nodes: &[Node<K, V>]) {
+    if let Some(node) = nodes.get(nodes.len() / 2) {
+        #[allow(unsafe_code)]
+        // SAFETY: Prefetching has no side effects and SSE is part of the x86_64 baseline.
+        unsafe {
+            _mm_prefetch::<_MM_HINT_T0>((node as *const Node<K, V>).cast());
+        }
+    }
+}
+
+/// Nodes which prefetch the roots of both subtrees whenever they are split
+struct Prefetching<'a, K, V>(&'a [Node<K, V>]);
+
+impl<K, V> Clone for Prefetching<'_, K, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K, V> Copy for Prefetching<'_, K, V> {}
+
+impl<'a, K, V> Subtree for Prefetching<'a, K, V>
+where
+    K: Ord,
+{
+    type Key = K;
+    type Bound = &'a K;
+    type Root = &'a Node<K, V>;
+
+    fn len(self) -> usize {
+        self.0.len()
+    }
+
+    fn split(self) -> Option<(Self, Self::Root, Self)> {
+        let (left, mid, right) = split(self.0)?;
+
+        prefetch(left);
+        prefetch(right);
+
+        Some((Self(left), mid, Self(right)))
+    }
+
+    fn start(root: Self::Root) -> Self::Bound {
+        &root.start
+    }
+
+    fn end(root: Self::Root) -> Self::Bound {
+        &root.end
+    }
+
+    fn max(root: Self::Root) -> Self::Bound {
+        &root.max
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use proptest::{collection::vec, test_runner::TestRunner};
+
+    #[test]
+    fn query_prefetch_random() {
+        const DOM: Range<i32> = -1000..1000;
+        const LEN: usize = 1000_usize;
+
+        TestRunner::default()
+            .run(
+                &(vec(DOM, LEN), vec(DOM, LEN), DOM, DOM),
+                |(start, end, query_start, query_end)| {
+                    let tree = ITree::<_, _>::new(
+                        start
+                            .iter()
+                            .zip(&end)
+                            .enumerate()
+                            .map(|(index, (&start, &end))| (start..end, index)),
+                    );
+
+                    let mut result1 = Vec::new();
+                    let _ = tree.query_prefetch(query_start..query_end, |entry| {
+                        result1.push(*entry.value());
+                        ControlFlow::Continue(())
+                    });
+
+                    let mut result2 = Vec::new();
+                    let _ = tree.query(query_start..query_end, |entry| {
+                        result2.push(*entry.value());
+                        ControlFlow::Continue(())
+                    });
+
+                    assert_eq!(result1, result2);
+
+                    Ok(())
+                },
+            )
+            .unwrap()
+    }
+}
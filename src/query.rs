@@ -1,27 +1,53 @@
-use std::ops::{ControlFlow, Range};
+use core::borrow::Borrow;
+use core::ops::{ControlFlow, Range};
+
+#[cfg(feature = "threads")]
+use std::panic::resume_unwind;
+#[cfg(feature = "threads")]
+use std::thread::scope;
 
 #[cfg(feature = "rayon")]
-use rayon::join;
+use rayon::{join, ThreadPool};
 
-use crate::{ITree, Item, Node};
+use crate::{ITree, Node};
 
 impl<K, V, S> ITree<K, V, S>
 where
     S: AsRef<[Node<K, V>]>,
 {
     /// Query for all intervals overlapping the given interval
-    pub fn query<'a, H>(&'a self, interval: Range<K>, handler: H) -> ControlFlow<()>
+    pub fn query<'a, H>(&'a self, interval: Range<K>, mut handler: H) -> ControlFlow<()>
     where
         K: Ord,
-        H: FnMut(&'a Item<K, V>) -> ControlFlow<()>,
+        H: FnMut(Entry<'a, K, V>) -> ControlFlow<()>,
     {
         let nodes = self.nodes.as_ref();
 
-        if !nodes.is_empty() {
-            query(&mut QueryArgs { interval, handler }, nodes)?;
-        }
+        traverse(&interval, nodes, &mut |node| handler(node.into()))
+    }
 
-        ControlFlow::Continue(())
+    /// Query for all intervals overlapping the given interval, scanning subtrees of at most `bucket_len` nodes linearly
+    ///
+    /// Each subtree is stored contiguously and sorted by interval start, so this does not require a different layout. Yields the same items as [`query`][Self::query], but possibly in a different order.
+    pub fn query_bucketed<'a, H>(
+        &'a self,
+        interval: Range<K>,
+        bucket_len: usize,
+        mut handler: H,
+    ) -> ControlFlow<()>
+    where
+        K: Ord,
+        H: FnMut(Entry<'a, K, V>) -> ControlFlow<()>,
+    {
+        let nodes = self.nodes.as_ref();
+
+        traverse_bucketed(
+            &interval,
+            nodes,
+            bucket_len,
+            &mut |node| handler(node.into()),
+            &mut |nodes, handler| scan(&interval, nodes, handler),
+        )
     }
 
     #[cfg(feature = "rayon")]
@@ -30,7 +56,7 @@ where
     where
         K: Ord + Send + Sync,
         V: Sync,
-        H: Fn(&'a Item<K, V>) -> ControlFlow<()> + Sync,
+        H: Fn(Entry<'a, K, V>) -> ControlFlow<()> + Sync,
     {
         let nodes = self.nodes.as_ref();
 
@@ -40,95 +66,379 @@ where
 
         ControlFlow::Continue(())
     }
+
+    #[cfg(feature = "rayon")]
+    /// Query for all intervals overlapping the given interval, in parallel using the given thread `pool`
+    pub fn par_query_in<'a, H>(
+        &'a self,
+        interval: Range<K>,
+        handler: H,
+        pool: &ThreadPool,
+    ) -> ControlFlow<()>
+    where
+        K: Ord + Send + Sync,
+        V: Sync,
+        H: Fn(Entry<'a, K, V>) -> ControlFlow<()> + Send + Sync,
+    {
+        let nodes = self.nodes.as_ref();
+
+        if !nodes.is_empty() {
+            pool.install(|| par_query(&QueryArgs { interval, handler }, nodes))?;
+        }
+
+        ControlFlow::Continue(())
+    }
+
+    #[cfg(feature = "threads")]
+    /// Query for all intervals overlapping the given interval, in parallel using up to `threads` scoped threads
+    ///
+    /// Requires the `threads` feature and uses only the standard library.
+    pub fn par_query_std<'a, H>(
+        &'a self,
+        interval: Range<K>,
+        handler: H,
+        threads: usize,
+    ) -> ControlFlow<()>
+    where
+        K: Ord + Sync,
+        V: Sync,
+        H: Fn(Entry<'a, K, V>) -> ControlFlow<()> + Sync,
+    {
+        let nodes = self.nodes.as_ref();
+
+        if !nodes.is_empty() {
+            scoped_query(&QueryArgs { interval, handler }, nodes, threads)?;
+        }
+
+        ControlFlow::Continue(())
+    }
+}
+
+/// An item visited by a query
+#[derive(Debug)]
+pub struct Entry<'a, K, V> {
+    start: &'a K,
+    end: &'a K,
+    value: &'a V,
+}
+
+impl<K, V> Clone for Entry<'_, K, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K, V> Copy for Entry<'_, K, V> {}
+
+impl<'a, K, V> Entry<'a, K, V> {
+    pub(crate) fn new(start: &'a K, end: &'a K, value: &'a V) -> Self {
+        Self { start, end, value }
+    }
+
+    /// The inclusive lower bound of the interval
+    pub fn start(&self) -> &'a K {
+        self.start
+    }
+
+    /// The exclusive upper bound of the interval
+    pub fn end(&self) -> &'a K {
+        self.end
+    }
+
+    /// The interval as a range of references to its bounds
+    pub fn interval(&self) -> Range<&'a K> {
+        self.start..self.end
+    }
+
+    /// The value associated with the interval
+    pub fn value(&self) -> &'a V {
+        self.value
+    }
+}
+
+impl<'a, K, V> From<&'a Node<K, V>> for Entry<'a, K, V> {
+    fn from(node: &'a Node<K, V>) -> Self {
+        Self::new(&node.start, &node.end, &node.value)
+    }
+}
+
+/// Splits `nodes` into the left subtree, the root and the right subtree unless it is empty
+#[inline(always)]
+pub(crate) fn split<T>(nodes: &[T]) -> Option<(&[T], &T, &[T])> {
+    if nodes.is_empty() {
+        return None;
+    }
+
+    let mid = nodes.len() / 2;
+
+    #[cfg(not(feature = "unsafe-opt"))]
+    {
+        Some((&nodes[..mid], &nodes[mid], &nodes[mid + 1..]))
+    }
+
+    #[cfg(feature = "unsafe-opt")]
+    #[allow(unsafe_code)]
+    // SAFETY: `nodes` is not empty, hence `mid < nodes.len()`.
+    unsafe {
+        Some((
+            nodes.get_unchecked(..mid),
+            nodes.get_unchecked(mid),
+            nodes.get_unchecked(mid + 1..),
+        ))
+    }
 }
 
-struct QueryArgs<K, H> {
-    interval: Range<K>,
-    handler: H,
+pub(crate) struct QueryArgs<K, H> {
+    pub(crate) interval: Range<K>,
+    pub(crate) handler: H,
 }
 
-fn query<'a, K, V, H>(args: &mut QueryArgs<K, H>, mut nodes: &'a [Node<K, V>]) -> ControlFlow<()>
+/// A contiguous subtree of an implicit interval tree whose root is stored at half its length
+///
+/// Implemented by each node representation so that they all share [`traverse`] and [`traverse_bucketed`].
+pub(crate) trait Subtree: Copy {
+    type Key: Ord;
+    type Bound: Borrow<Self::Key>;
+    type Root: Copy;
+
+    fn len(self) -> usize;
+
+    /// Splits the subtree into its left subtree, its root and its right subtree unless it is empty
+    fn split(self) -> Option<(Self, Self::Root, Self)>;
+
+    fn start(root: Self::Root) -> Self::Bound;
+
+    fn end(root: Self::Root) -> Self::Bound;
+
+    /// The maximum of the interval upper bounds in the subtree below `root`
+    fn max(root: Self::Root) -> Self::Bound;
+}
+
+impl<'a, K, V> Subtree for &'a [Node<K, V>]
 where
     K: Ord,
-    H: FnMut(&'a (Range<K>, V)) -> ControlFlow<()>,
+{
+    type Key = K;
+    type Bound = &'a K;
+    type Root = &'a Node<K, V>;
+
+    #[inline(always)]
+    fn len(self) -> usize {
+        <[_]>::len(self)
+    }
+
+    #[inline(always)]
+    fn split(self) -> Option<(Self, Self::Root, Self)> {
+        split(self)
+    }
+
+    #[inline(always)]
+    fn start(root: Self::Root) -> Self::Bound {
+        &root.start
+    }
+
+    #[inline(always)]
+    fn end(root: Self::Root) -> Self::Bound {
+        &root.end
+    }
+
+    #[inline(always)]
+    fn max(root: Self::Root) -> Self::Bound {
+        &root.max
+    }
+}
+
+/// Reports `root` if it overlaps `interval` and decides whether to descend into the `left` and `right` subtrees
+#[inline(always)]
+pub(crate) fn visit<T, H>(
+    interval: &Range<T::Key>,
+    left: T,
+    root: T::Root,
+    right: T,
+    handler: &mut H,
+) -> ControlFlow<(), (bool, bool)>
+where
+    T: Subtree,
+    H: FnMut(T::Root) -> ControlFlow<()>,
+{
+    let mut go_left = false;
+    let mut go_right = false;
+
+    if interval.start < *T::max(root).borrow() {
+        if left.len() != 0 {
+            go_left = true;
+        }
+
+        if interval.end > *T::start(root).borrow() {
+            if right.len() != 0 {
+                go_right = true;
+            }
+
+            if interval.start < *T::end(root).borrow() {
+                handler(root)?;
+            }
+        }
+    }
+
+    ControlFlow::Continue((go_left, go_right))
+}
+
+/// Passes the roots of all nodes in `subtree` overlapping `interval` to `handler`
+pub(crate) fn traverse<T, H>(
+    interval: &Range<T::Key>,
+    mut subtree: T,
+    handler: &mut H,
+) -> ControlFlow<()>
+where
+    T: Subtree,
+    H: FnMut(T::Root) -> ControlFlow<()>,
 {
     loop {
-        let (left, [mid, right @ ..]) = nodes.split_at(nodes.len() / 2) else {
-            unreachable!()
+        let Some((left, root, right)) = subtree.split() else {
+            return ControlFlow::Continue(());
         };
 
-        let mut go_left = false;
-        let mut go_right = false;
+        match visit(interval, left, root, right, handler)? {
+            (true, true) => {
+                traverse(interval, left, handler)?;
 
-        if args.interval.start < mid.1 {
-            if !left.is_empty() {
-                go_left = true;
+                subtree = right;
             }
+            (true, false) => subtree = left,
+            (false, true) => subtree = right,
+            (false, false) => return ControlFlow::Continue(()),
+        }
+    }
+}
 
-            if args.interval.end > (mid.0).0.start {
-                if !right.is_empty() {
-                    go_right = true;
-                }
+/// Like [`traverse`], but passes subtrees of at most `bucket_len` nodes which can contain overlapping intervals to `scan`
+pub(crate) fn traverse_bucketed<T, H, F>(
+    interval: &Range<T::Key>,
+    mut subtree: T,
+    bucket_len: usize,
+    handler: &mut H,
+    scan: &mut F,
+) -> ControlFlow<()>
+where
+    T: Subtree,
+    H: FnMut(T::Root) -> ControlFlow<()>,
+    F: FnMut(T, &mut H) -> ControlFlow<()>,
+{
+    loop {
+        let Some((left, root, right)) = subtree.split() else {
+            return ControlFlow::Continue(());
+        };
 
-                if args.interval.start < (mid.0).0.end {
-                    (args.handler)(&mid.0)?;
-                }
+        if subtree.len() <= bucket_len {
+            if interval.start >= *T::max(root).borrow() {
+                return ControlFlow::Continue(());
             }
+
+            return scan(subtree, handler);
         }
 
-        match (go_left, go_right) {
+        match visit(interval, left, root, right, handler)? {
             (true, true) => {
-                query(args, left)?;
+                traverse_bucketed(interval, left, bucket_len, handler, scan)?;
 
-                nodes = right;
+                subtree = right;
             }
-            (true, false) => nodes = left,
-            (false, true) => nodes = right,
+            (true, false) => subtree = left,
+            (false, true) => subtree = right,
             (false, false) => return ControlFlow::Continue(()),
         }
     }
 }
 
+fn scan<'a, K, V, H>(
+    interval: &Range<K>,
+    nodes: &'a [Node<K, V>],
+    handler: &mut H,
+) -> ControlFlow<()>
+where
+    K: Ord,
+    H: FnMut(&'a Node<K, V>) -> ControlFlow<()>,
+{
+    for node in nodes {
+        if interval.end <= node.start {
+            break;
+        }
+
+        if interval.start < node.end {
+            handler(node)?;
+        }
+    }
+
+    ControlFlow::Continue(())
+}
+
 #[cfg(feature = "rayon")]
 fn par_query<'a, K, V, H>(args: &QueryArgs<K, H>, mut nodes: &'a [Node<K, V>]) -> ControlFlow<()>
 where
     K: Ord + Send + Sync,
     V: Sync,
-    H: Fn(&'a (Range<K>, V)) -> ControlFlow<()> + Sync,
+    H: Fn(Entry<'a, K, V>) -> ControlFlow<()> + Sync,
 {
     loop {
-        let (left, [mid, right @ ..]) = nodes.split_at(nodes.len() / 2) else {
-            unreachable!()
+        let Some((left, mid, right)) = split(nodes) else {
+            return ControlFlow::Continue(());
         };
 
-        let mut go_left = false;
-        let mut go_right = false;
-
-        if args.interval.start < mid.1 {
-            if !left.is_empty() {
-                go_left = true;
-            }
+        match visit(&args.interval, left, mid, right, &mut |node| {
+            (args.handler)(node.into())
+        })? {
+            (true, true) => {
+                let (left, right) = join(|| par_query(args, left), || par_query(args, right));
 
-            if args.interval.end > (mid.0).0.start {
-                if !right.is_empty() {
-                    go_right = true;
-                }
+                left?;
+                right?;
 
-                if args.interval.start < (mid.0).0.end {
-                    (args.handler)(&mid.0)?;
-                }
+                return ControlFlow::Continue(());
             }
+            (true, false) => nodes = left,
+            (false, true) => nodes = right,
+            (false, false) => return ControlFlow::Continue(()),
         }
+    }
+}
 
-        match (go_left, go_right) {
-            (true, true) => {
-                let (left, right) = join(|| par_query(args, left), || par_query(args, right));
+#[cfg(feature = "threads")]
+fn scoped_query<'a, K, V, H>(
+    args: &QueryArgs<K, H>,
+    mut nodes: &'a [Node<K, V>],
+    threads: usize,
+) -> ControlFlow<()>
+where
+    K: Ord + Sync,
+    V: Sync,
+    H: Fn(Entry<'a, K, V>) -> ControlFlow<()> + Sync,
+{
+    loop {
+        let Some((left, mid, right)) = split(nodes) else {
+            return ControlFlow::Continue(());
+        };
+
+        match visit(&args.interval, left, mid, right, &mut |node| {
+            (args.handler)(node.into())
+        })? {
+            (true, true) if threads > 1 => {
+                let (left, right) = scope(|scope| {
+                    let left = scope.spawn(|| scoped_query(args, left, threads / 2));
+                    let right = scoped_query(args, right, threads - threads / 2);
+
+                    (left.join().unwrap_or_else(|err| resume_unwind(err)), right)
+                });
 
                 left?;
                 right?;
 
                 return ControlFlow::Continue(());
             }
+            (true, true) => {
+                scoped_query(args, left, 1)?;
+
+                nodes = right;
+            }
             (true, false) => nodes = left,
             (false, true) => nodes = right,
             (false, false) => return ControlFlow::Continue(()),
@@ -137,10 +447,11 @@ where
 }
 
 #[cfg(test)]
+#[allow(unused_must_use)]
 mod tests {
     use super::*;
 
-    #[cfg(feature = "rayon")]
+    #[cfg(any(feature = "rayon", feature = "threads"))]
     use std::sync::Mutex;
 
     use proptest::{collection::vec, test_runner::TestRunner};
@@ -162,15 +473,15 @@ mod tests {
                     );
 
                     let mut result1 = Vec::new();
-                    tree.query(query_start..query_end, |(range, ())| {
-                        result1.push(range);
+                    tree.query(query_start..query_end, |entry| {
+                        result1.push(entry.interval());
                         ControlFlow::Continue(())
                     });
 
                     let mut result2 = tree
                         .iter()
-                        .filter(|(range, ())| query_end > range.start && query_start < range.end)
-                        .map(|(range, ())| range)
+                        .filter(|node| query_end > node.start && query_start < node.end)
+                        .map(Node::interval)
                         .collect::<Vec<_>>();
 
                     result1.sort_unstable_by_key(|range| (range.start, range.end));
@@ -183,6 +494,44 @@ mod tests {
             .unwrap()
     }
 
+    #[test]
+    fn query_bucketed_random() {
+        const DOM: Range<i32> = -1000..1000;
+        const LEN: usize = 1000_usize;
+
+        TestRunner::default()
+            .run(
+                &(vec(DOM, LEN), vec(DOM, LEN), DOM, DOM, 0_usize..100),
+                |(start, end, query_start, query_end, bucket_len)| {
+                    let tree = ITree::<_, _>::new(
+                        start
+                            .iter()
+                            .zip(&end)
+                            .map(|(&start, &end)| (start..end, ())),
+                    );
+
+                    let mut result1 = Vec::new();
+                    let _ = tree.query_bucketed(query_start..query_end, bucket_len, |entry| {
+                        result1.push(entry.interval());
+                        ControlFlow::Continue(())
+                    });
+
+                    let mut result2 = Vec::new();
+                    let _ = tree.query(query_start..query_end, |entry| {
+                        result2.push(entry.interval());
+                        ControlFlow::Continue(())
+                    });
+
+                    result1.sort_unstable_by_key(|range| (range.start, range.end));
+                    result2.sort_unstable_by_key(|range| (range.start, range.end));
+                    assert_eq!(result1, result2);
+
+                    Ok(())
+                },
+            )
+            .unwrap()
+    }
+
     #[cfg(feature = "rayon")]
     #[test]
     fn par_query_random() {
@@ -201,16 +550,86 @@ mod tests {
                     );
 
                     let result1 = Mutex::new(Vec::new());
-                    tree.par_query(query_start..query_end, |(range, ())| {
-                        result1.lock().unwrap().push(range);
+                    tree.par_query(query_start..query_end, |entry| {
+                        result1.lock().unwrap().push(entry.interval());
                         ControlFlow::Continue(())
                     });
                     let mut result1 = result1.into_inner().unwrap();
 
                     let mut result2 = tree
                         .iter()
-                        .filter(|(range, ())| query_end > range.start && query_start < range.end)
-                        .map(|(range, ())| range)
+                        .filter(|node| query_end > node.start && query_start < node.end)
+                        .map(Node::interval)
+                        .collect::<Vec<_>>();
+
+                    result1.sort_unstable_by_key(|range| (range.start, range.end));
+                    result2.sort_unstable_by_key(|range| (range.start, range.end));
+                    assert_eq!(result1, result2);
+
+                    Ok(())
+                },
+            )
+            .unwrap()
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn par_query_in_pool() {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(2)
+            .build()
+            .unwrap();
+
+        let tree = ITree::<_, _>::new((0..1000).map(|start| (start..start + 10, ())));
+
+        let result = Mutex::new(Vec::new());
+        let _ = tree.par_query_in(
+            100..105,
+            |entry| {
+                result.lock().unwrap().push(*entry.start());
+                ControlFlow::Continue(())
+            },
+            &pool,
+        );
+        let mut result = result.into_inner().unwrap();
+
+        result.sort_unstable();
+        assert_eq!(result, (91..105).collect::<Vec<_>>());
+    }
+
+    #[cfg(feature = "threads")]
+    #[test]
+    fn par_query_std_random() {
+        const DOM: Range<i32> = -1000..1000;
+        const LEN: usize = 1000_usize;
+
+        TestRunner::default()
+            .run(
+                &(vec(DOM, LEN), vec(DOM, LEN), DOM, DOM, 1_usize..8),
+                |(start, end, query_start, query_end, threads)| {
+                    let tree = ITree::<_, _>::par_new_std(
+                        start
+                            .iter()
+                            .zip(&end)
+                            .map(|(&start, &end)| (start..end, ())),
+                        threads,
+                    );
+
+                    let result1 = Mutex::new(Vec::new());
+                    let _ = tree.par_query_std(
+                        query_start..query_end,
+                        |entry| {
+                            result1.lock().unwrap().push(entry.interval());
+                            ControlFlow::Continue(())
+                        },
+                        threads,
+                    );
+                    let mut result1 = result1.into_inner().unwrap();
+
+                    let mut result2 = tree
+                        .iter()
+                        .filter(|node| query_end > node.start && query_start < node.end)
+                        .map(Node::interval)
                         .collect::<Vec<_>>();
 
                     result1.sort_unstable_by_key(|range| (range.start, range.end));
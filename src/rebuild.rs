@@ -0,0 +1,318 @@
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use core::ops::Range;
+
+use crate::sort::{cmp, merge_max, node, sort, update_max};
+use crate::{ITree, InvalidTree, Item, Node};
+
+impl<K, V, S> ITree<K, V, S>
+where
+    K: Ord + Clone,
+    S: Into<Vec<Node<K, V>>> + From<Vec<Node<K, V>>>,
+{
+    /// Retain only the items for which `pred` returns `true`
+    ///
+    /// Does not need to sort the remaining items again. A `Vec` typically keeps its allocation, but a `Box<[_]>` is reallocated when items are removed as a boxed slice cannot shrink in place.
+    pub fn retain<F>(self, mut pred: F) -> Self
+    where
+        F: FnMut(&Item<K, V>) -> bool,
+    {
+        let nodes: Vec<_> = self.nodes.into();
+
+        let mut nodes = nodes
+            .into_iter()
+            .map(Node::into_item)
+            .filter(|item| pred(item))
+            .map(node)
+            .collect::<Vec<_>>();
+
+        if !nodes.is_empty() {
+            update_max(&mut nodes);
+        }
+
+        Self {
+            nodes: nodes.into(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Merge touching or overlapping intervals whose values are compatible according to `merge_if`
+    ///
+    /// Each item is merged into the earliest preceding item which still touches or overlaps it and for which `merge_if(&earlier, &later)` returns `true`, keeping the value of the earlier item.
+    pub fn coalesce<F>(self, merge_if: F) -> Self
+    where
+        F: Fn(&V, &V) -> bool,
+    {
+        let nodes: Vec<_> = self.nodes.into();
+
+        let mut merged = Vec::<Node<K, V>>::with_capacity(nodes.len());
+        let mut active = Vec::<usize>::new();
+
+        for node in nodes {
+            active.retain(|&index| merged[index].end >= node.start);
+
+            let target = active
+                .iter()
+                .copied()
+                .find(|&index| merge_if(&merged[index].value, &node.value));
+
+            match target {
+                Some(index) => {
+                    let end = &mut merged[index].end;
+
+                    if *end < node.end {
+                        *end = node.end;
+                    }
+                }
+                None => {
+                    active.push(merged.len());
+                    merged.push(node);
+                }
+            }
+        }
+
+        for node in &mut merged {
+            node.max = node.end.clone();
+        }
+
+        sort(&mut merged);
+
+        if !merged.is_empty() {
+            update_max(&mut merged);
+        }
+
+        Self {
+            nodes: merged.into(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<K, V, S> ITree<K, V, S>
+where
+    K: Ord + Clone,
+    S: AsMut<[Node<K, V>]>,
+{
+    /// Re-establish the tree invariants after the nodes were modified via [`as_mut`][AsMut::as_mut]
+    ///
+    /// Sorts the items again and recomputes the maximum of the interval upper bounds, without allocating.
+    pub fn rebuild_in_place(&mut self) {
+        let nodes = self.nodes.as_mut();
+
+        for node in &mut *nodes {
+            node.max = node.end.clone();
+        }
+
+        sort(nodes);
+
+        if !nodes.is_empty() {
+            update_max(nodes);
+        }
+    }
+
+    /// Change the end of the interval at the given `index`
+    ///
+    /// Only the maximum of the interval upper bounds of the node and its ancestors is recomputed which takes time logarithmic in the number of nodes.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds.
+    pub fn update_end(&mut self, index: usize, end: K) {
+        let nodes = self.nodes.as_mut();
+
+        nodes[index].end = end;
+
+        update_path(nodes, index);
+    }
+
+    /// Change the interval at the given `index` if its start keeps the items sorted
+    ///
+    /// Like [`update_end`][Self::update_end], this only recomputes the maximum of the interval upper bounds of the node and its ancestors. If the new start would break the ordering, the tree is not modified and the offending node is reported instead.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds.
+    pub fn update_interval(&mut self, index: usize, interval: Range<K>) -> Result<(), InvalidTree> {
+        let nodes = self.nodes.as_mut();
+
+        if index != 0 && nodes[index - 1].start > interval.start {
+            return Err(InvalidTree::Unsorted(index));
+        }
+
+        if let Some(next) = nodes.get(index + 1) {
+            if interval.start > next.start {
+                return Err(InvalidTree::Unsorted(index + 1));
+            }
+        }
+
+        let node = &mut nodes[index];
+        node.start = interval.start;
+        node.end = interval.end;
+
+        update_path(nodes, index);
+
+        Ok(())
+    }
+}
+
+fn update_path<K, V>(nodes: &mut [Node<K, V>], index: usize)
+where
+    K: Ord + Clone,
+{
+    let (left, [mid, right @ ..]) = nodes.split_at_mut(nodes.len() / 2) else {
+        unreachable!()
+    };
+
+    if index < left.len() {
+        update_path(left, index);
+    } else if index > left.len() {
+        update_path(right, index - left.len() - 1);
+    }
+
+    mid.max = mid.end.clone();
+
+    if !left.is_empty() {
+        merge_max(mid, left);
+    }
+
+    if !right.is_empty() {
+        merge_max(mid, right);
+    }
+}
+
+/// Appends the new items and re-establishes the tree invariants
+///
+/// Only the new items are sorted and then merged with the existing ones, which is efficient for adding a few items to a large tree.
+impl<K, V> Extend<Item<K, V>> for ITree<K, V, Vec<Node<K, V>>>
+where
+    K: Ord + Clone,
+{
+    fn extend<I>(&mut self, items: I)
+    where
+        I: IntoIterator<Item = Item<K, V>>,
+    {
+        let nodes = &mut self.nodes;
+        let len = nodes.len();
+
+        nodes.extend(items.into_iter().map(node));
+
+        if nodes.len() == len {
+            return;
+        }
+
+        for node in &mut nodes[..len] {
+            node.max = node.end.clone();
+        }
+
+        nodes[len..].sort_unstable_by(cmp);
+
+        // The stable sort detects the two sorted runs and merges them in linear time.
+        nodes.sort_by(cmp);
+
+        update_max(nodes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retain_recomputes_max() {
+        let tree = ITree::<_, _>::new([(0..10, 0), (1..2, 1), (2..3, 2), (3..4, 3)]);
+
+        let tree = tree.retain(|(_interval, value)| *value != 0);
+        assert!(tree.is_valid());
+        assert!(tree.iter().eq(&[(1..2, 1), (2..3, 2), (3..4, 3)]));
+    }
+
+    #[test]
+    fn coalesce_compatible_intervals() {
+        let tree = ITree::<_, _>::new([
+            (0..2, 'a'),
+            (2..4, 'a'),
+            (3..6, 'b'),
+            (5..7, 'a'),
+            (4..8, 'b'),
+            (9..10, 'b'),
+        ]);
+
+        let tree = tree.coalesce(|lhs, rhs| lhs == rhs);
+        assert!(tree.is_valid());
+        assert!(tree
+            .iter()
+            .eq(&[(0..4, 'a'), (3..8, 'b'), (5..7, 'a'), (9..10, 'b')]));
+    }
+
+    #[test]
+    fn extend_merges_new_items() {
+        let mut tree = ITree::<_, _, Vec<_>>::new([(0..1, 0), (2..3, 2), (4..5, 4)]);
+
+        tree.extend([(5..6, 5), (1..2, 1), (3..4, 3)]);
+        assert!(tree.is_valid());
+        assert!(tree.iter().eq(&[
+            (0..1, 0),
+            (1..2, 1),
+            (2..3, 2),
+            (3..4, 3),
+            (4..5, 4),
+            (5..6, 5)
+        ]));
+    }
+
+    #[test]
+    fn update_end_and_interval() {
+        let mut tree = ITree::<_, _>::new((0..100).map(|start| (start..start + 1, start)));
+
+        tree.update_end(17, 1000);
+        assert!(tree.is_valid());
+        assert_eq!(tree[50].max, 1000);
+
+        tree.update_end(17, 18);
+        assert!(tree.is_valid());
+        assert_eq!(tree[50].max, 100);
+
+        assert_eq!(
+            tree.update_interval(42, 44..45),
+            Err(InvalidTree::Unsorted(43))
+        );
+        assert_eq!(
+            tree.update_interval(42, 40..45),
+            Err(InvalidTree::Unsorted(42))
+        );
+
+        tree.update_interval(42, 41..200).unwrap();
+        assert!(tree.is_valid());
+        assert_eq!(tree[50].max, 200);
+    }
+
+    #[test]
+    fn update_values_in_place() {
+        let mut tree = ITree::<_, _>::new([(0..1, 0), (1..2, 1), (2..3, 2)]);
+
+        for (interval, value) in tree.iter_mut() {
+            *value += interval.end;
+        }
+
+        for value in tree.values_mut() {
+            *value *= 2;
+        }
+
+        assert!(tree.iter().eq(&[(0..1, 2), (1..2, 6), (2..3, 10)]));
+    }
+
+    #[test]
+    fn rebuild_in_place_after_mutation() {
+        let mut tree = ITree::<_, _>::new([(0..1, 0), (1..2, 1), (2..3, 2)]);
+
+        for node in tree.as_mut() {
+            node.start = 10 - node.start;
+            node.end = node.start + 5;
+        }
+
+        tree.rebuild_in_place();
+        assert!(tree.is_valid());
+        assert!(tree.iter().eq(&[(8..13, 2), (9..14, 1), (10..15, 0)]));
+    }
+}
@@ -0,0 +1,145 @@
+use std::sync::mpsc::{channel, Receiver, Sender, TryRecvError};
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
+use std::thread::spawn;
+
+use crate::{ITree, Item, Node};
+
+/// Serves snapshots of a tree while a replacement is built in the background
+///
+/// Whenever a rebuild finishes, the new tree atomically replaces the current one. Earlier snapshots obtained via [`load`][Self::load] remain valid until dropped. Requires the `std` feature.
+#[derive(Debug)]
+pub struct RebuildingITree<K, V, S = Box<[Node<K, V>]>> {
+    current: Arc<RwLock<Arc<ITree<K, V, S>>>>,
+    pending: Mutex<Option<Receiver<()>>>,
+}
+
+impl<K, V, S> RebuildingITree<K, V, S>
+where
+    K: Ord + Clone + Send + Sync + 'static,
+    V: Send + Sync + 'static,
+    S: AsMut<[Node<K, V>]> + FromIterator<Node<K, V>> + Send + Sync + 'static,
+{
+    /// Start serving the given `tree`
+    pub fn new(tree: ITree<K, V, S>) -> Self {
+        Self {
+            current: Arc::new(RwLock::new(Arc::new(tree))),
+            pending: Mutex::new(None),
+        }
+    }
+
+    /// A snapshot of the current tree
+    pub fn load(&self) -> Arc<ITree<K, V, S>> {
+        self.current
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Replace the current tree immediately
+    pub fn store(&self, tree: ITree<K, V, S>) {
+        store(&self.current, tree);
+    }
+
+    /// Whether a rebuild is still in progress
+    pub fn is_rebuilding(&self) -> bool {
+        let mut pending = self.pending.lock().unwrap_or_else(PoisonError::into_inner);
+
+        is_rebuilding(&mut pending)
+    }
+
+    /// Construct a new tree from the given `items` on a background thread
+    ///
+    /// Returns `false` without starting a rebuild if another one is still in progress.
+    pub fn rebuild<I>(&self, items: I) -> bool
+    where
+        I: IntoIterator<Item = Item<K, V>> + Send + 'static,
+    {
+        self.start(|current, done| {
+            spawn(move || {
+                store(&current, ITree::new(items));
+                let _ = done.send(());
+            });
+        })
+    }
+
+    #[cfg(feature = "rayon")]
+    /// Construct a new tree from the given `items` as a task in the current [thread pool][rayon::ThreadPool]
+    ///
+    /// Requires the `rayon` feature and builds the tree using [`par_new`][ITree::par_new]. Returns `false` without starting a rebuild if another one is still in progress.
+    pub fn par_rebuild<I>(&self, items: I) -> bool
+    where
+        I: IntoIterator<Item = Item<K, V>> + Send + 'static,
+    {
+        self.start(|current, done| {
+            rayon::spawn(move || {
+                store(&current, ITree::par_new(items));
+                let _ = done.send(());
+            });
+        })
+    }
+
+    /// Block until the rebuild in progress, if any, has finished
+    pub fn wait(&self) {
+        let pending = self
+            .pending
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+
+        if let Some(pending) = pending {
+            let _ = pending.recv();
+        }
+    }
+
+    fn start<F>(&self, f: F) -> bool
+    where
+        F: FnOnce(Arc<RwLock<Arc<ITree<K, V, S>>>>, Sender<()>),
+    {
+        let mut pending = self.pending.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if is_rebuilding(&mut pending) {
+            return false;
+        }
+
+        let (done, finished) = channel();
+        f(self.current.clone(), done);
+        *pending = Some(finished);
+
+        true
+    }
+}
+
+fn store<T>(current: &RwLock<Arc<T>>, tree: T) {
+    *current.write().unwrap_or_else(PoisonError::into_inner) = Arc::new(tree);
+}
+
+fn is_rebuilding(pending: &mut Option<Receiver<()>>) -> bool {
+    if let Some(finished) = pending {
+        if let Err(TryRecvError::Empty) = finished.try_recv() {
+            return true;
+        }
+
+        *pending = None;
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rebuild_in_background() {
+        let tree = RebuildingITree::<_, _>::new(ITree::new([(0..1, 0)]));
+
+        let snapshot = tree.load();
+
+        assert!(tree.rebuild((0..100).map(|start| (start..start + 1, start))));
+        tree.wait();
+        assert!(!tree.is_rebuilding());
+
+        assert_eq!(snapshot.len(), 1);
+        assert_eq!(tree.load().len(), 100);
+    }
+}
@@ -0,0 +1,258 @@
+use alloc::borrow::{Cow, ToOwned};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Deref;
+
+use serde::{
+    de::{Error as _, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{ITree, Node};
+
+/// Serializes the items as `{ "start": .., "end": .., "value": .. }` records for human-readable formats and the nodes otherwise
+impl<K, V, S> Serialize for ITree<K, V, S>
+where
+    K: Serialize,
+    V: Serialize,
+    S: AsRef<[Node<K, V>]> + Serialize,
+{
+    fn serialize<Ser>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error>
+    where
+        Ser: Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.collect_seq(self.nodes.as_ref().iter().map(|node| ItemRef {
+                start: &node.start,
+                end: &node.end,
+                value: &node.value,
+            }))
+        } else {
+            self.nodes.serialize(serializer)
+        }
+    }
+}
+
+/// Rebuilds the tree from its items for human-readable formats and [checks][ITree::check] the nodes otherwise
+///
+/// `K: Clone` is required to rebuild the tree and the rebuilt nodes are converted into the storage `S` from a `Vec`, so that e.g. `Cow<[Node<K, V>]>` can be deserialized as well.
+impl<'de, K, V, S> Deserialize<'de> for ITree<K, V, S>
+where
+    K: Ord + Clone + Deserialize<'de>,
+    V: Deserialize<'de>,
+    S: AsRef<[Node<K, V>]> + From<Vec<Node<K, V>>> + Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            let items = Vec::<ItemOwned<K, V>>::deserialize(deserializer)?;
+
+            let tree = ITree::<K, V, Vec<_>>::new(
+                items
+                    .into_iter()
+                    .map(|item| (item.start..item.end, item.value)),
+            );
+
+            Ok(Self::new_unchecked(tree.nodes.into()))
+        } else {
+            let nodes = S::deserialize(deserializer)?;
+
+            Self::from_nodes(nodes).map_err(D::Error::custom)
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename = "Item")]
+struct ItemRef<'a, K, V> {
+    start: &'a K,
+    end: &'a K,
+    value: &'a V,
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "Item")]
+struct ItemOwned<K, V> {
+    start: K,
+    end: K,
+    value: V,
+}
+
+/// A string value which borrows from the input when deserialized if possible
+///
+/// Requires the `serde` feature. Contrary to `Cow<'de, str>`, this does not allocate unless the input contains escape sequences, so that trees with string values can be loaded from an in-memory buffer without one allocation per node. `&'de str` values work as well but fail on escaped strings.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CowStr<'a>(pub Cow<'a, str>);
+
+impl Deref for CowStr<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Serialize for CowStr<'_> {
+    fn serialize<Ser>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error>
+    where
+        Ser: Serializer,
+    {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for CowStr<'de> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(CowStrVisitor)
+    }
+}
+
+struct CowStrVisitor;
+
+impl<'de> Visitor<'de> for CowStrVisitor {
+    type Value = CowStr<'de>;
+
+    fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str("a string")
+    }
+
+    fn visit_borrowed_str<E>(self, value: &'de str) -> Result<Self::Value, E> {
+        Ok(CowStr(Cow::Borrowed(value)))
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E> {
+        Ok(CowStr(Cow::Owned(value.to_owned())))
+    }
+
+    fn visit_string<E>(self, value: String) -> Result<Self::Value, E> {
+        Ok(CowStr(Cow::Owned(value)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde::de::value::{BorrowedStrDeserializer, Error, StringDeserializer};
+    use serde_test::{assert_de_tokens_error, assert_tokens, Compact, Configure, Token};
+
+    fn assert_deserialize<'de, T: Deserialize<'de>>() {}
+
+    fn node_tokens(start: i32, end: i32, max: i32, value: char) -> [Token; 10] {
+        [
+            Token::Struct {
+                name: "Node",
+                len: 4,
+            },
+            Token::Str("start"),
+            Token::I32(start),
+            Token::Str("end"),
+            Token::I32(end),
+            Token::Str("max"),
+            Token::I32(max),
+            Token::Str("value"),
+            Token::Char(value),
+            Token::StructEnd,
+        ]
+    }
+
+    fn tree_tokens(nodes: [[Token; 10]; 2]) -> Vec<Token> {
+        let mut tokens = vec![Token::Seq { len: Some(2) }];
+        tokens.extend(nodes.into_iter().flatten());
+        tokens.push(Token::SeqEnd);
+        tokens
+    }
+
+    #[test]
+    fn reject_invalid_nodes() {
+        assert_de_tokens_error::<Compact<ITree<i32, char>>>(
+            &tree_tokens([node_tokens(1, 2, 2, 'a'), node_tokens(0, 1, 2, 'b')]),
+            "node 1 starts before its predecessor",
+        );
+
+        assert_de_tokens_error::<Compact<ITree<i32, char>>>(
+            &tree_tokens([node_tokens(0, 5, 5, 'a'), node_tokens(1, 2, 2, 'b')]),
+            "node 1 has an invalid maximum end",
+        );
+    }
+
+    #[test]
+    fn human_readable_records() {
+        let tree = ITree::<_, _>::new([(1..2, 'b'), (0..5, 'a')]);
+
+        let json = serde_json::to_string(&tree).unwrap();
+        assert_eq!(
+            json,
+            r#"[{"start":0,"end":5,"value":"a"},{"start":1,"end":2,"value":"b"}]"#
+        );
+
+        let other = serde_json::from_str::<ITree<i32, char>>(
+            r#"[{"start":1,"end":2,"value":"b"},{"start":0,"end":5,"value":"a"}]"#,
+        )
+        .unwrap();
+        assert!(other.is_valid());
+        assert_eq!(other, tree);
+    }
+
+    #[test]
+    fn compact_nodes() {
+        let tree = ITree::<_, _>::new([(1..2, 'b'), (0..5, 'a')]);
+
+        assert_tokens(
+            &tree.compact(),
+            &tree_tokens([node_tokens(0, 5, 5, 'a'), node_tokens(1, 2, 5, 'b')]),
+        );
+    }
+
+    #[test]
+    fn roundtrip() {
+        let tree = ITree::<_, _>::new([(2..3, 'c'), (0..5, 'a'), (1..2, 'b')]);
+
+        let json = serde_json::to_string(&tree).unwrap();
+        let other = serde_json::from_str::<ITree<i32, char>>(&json).unwrap();
+        assert!(other.is_valid());
+        assert_eq!(other, tree);
+
+        let other = serde_json::from_str::<ITree<i32, char, Cow<'_, [Node<_, _>]>>>(&json).unwrap();
+        assert!(other.is_valid());
+        assert_eq!(other, tree);
+    }
+
+    #[test]
+    fn borrowed_string_values() {
+        assert_deserialize::<ITree<u32, &str>>();
+        assert_deserialize::<ITree<u32, CowStr<'_>>>();
+
+        let value = CowStr::deserialize(BorrowedStrDeserializer::<Error>::new("label")).unwrap();
+        assert!(matches!(value.0, Cow::Borrowed("label")));
+
+        let value =
+            CowStr::deserialize(StringDeserializer::<Error>::new("label".to_owned())).unwrap();
+        assert!(matches!(value.0, Cow::Owned(_)));
+        assert_eq!(&*value, "label");
+    }
+
+    #[test]
+    fn borrow_string_values_from_buffer() {
+        let json = r#"[{"start":0,"end":5,"value":"gene"},{"start":1,"end":2,"value":"exon"},{"start":2,"end":3,"value":"say \"hi\""}]"#;
+
+        let tree = serde_json::from_str::<ITree<u32, CowStr<'_>>>(json).unwrap();
+        assert!(tree.is_valid());
+
+        let values = tree.iter().map(|node| &node.value.0).collect::<Vec<_>>();
+        assert!(matches!(values[0], Cow::Borrowed("gene")));
+        assert!(matches!(values[1], Cow::Borrowed("exon")));
+        assert!(matches!(values[2], Cow::Owned(value) if value == "say \"hi\""));
+
+        let Cow::Borrowed(value) = values[0] else {
+            unreachable!()
+        };
+        assert!(json.as_bytes().as_ptr_range().contains(&value.as_ptr()));
+    }
+}
@@ -0,0 +1,133 @@
+use alloc::boxed::Box;
+use core::ops::{ControlFlow, Range};
+
+use crate::query::split;
+use crate::{ITree, Node};
+
+/// Interval tree storing only intervals without associated values
+///
+/// As `()` is zero-sized, the nodes consist only of the interval bounds and the maximum.
+pub type ITreeSet<K, S = Box<[Node<K, ()>]>> = ITree<K, (), S>;
+
+impl<K, S> ITree<K, (), S>
+where
+    K: Ord + Clone,
+    S: AsMut<[Node<K, ()>]> + FromIterator<Node<K, ()>>,
+{
+    /// Construct a new set by sorting the given `intervals`
+    pub fn from_intervals<I>(intervals: I) -> Self
+    where
+        I: IntoIterator<Item = Range<K>>,
+    {
+        Self::new(intervals.into_iter().map(|interval| (interval, ())))
+    }
+}
+
+impl<K, S> ITree<K, (), S>
+where
+    S: AsRef<[Node<K, ()>]>,
+{
+    /// Iterate over all intervals in the order of their starts
+    pub fn intervals(&self) -> impl DoubleEndedIterator<Item = Range<&K>> + ExactSizeIterator {
+        self.iter().map(Node::interval)
+    }
+
+    /// Whether any interval overlaps the given interval
+    pub fn overlaps(&self, interval: Range<K>) -> bool
+    where
+        K: Ord,
+    {
+        self.query(interval, |_entry| ControlFlow::Break(()))
+            .is_break()
+    }
+
+    /// Whether any interval contains the given `point`
+    pub fn contains(&self, point: &K) -> bool
+    where
+        K: Ord,
+    {
+        let nodes = self.nodes.as_ref();
+
+        !nodes.is_empty() && contains(nodes, point)
+    }
+}
+
+fn contains<K>(mut nodes: &[Node<K, ()>], point: &K) -> bool
+where
+    K: Ord,
+{
+    loop {
+        let Some((left, mid, right)) = split(nodes) else {
+            return false;
+        };
+
+        if *point >= mid.max {
+            return false;
+        }
+
+        if mid.start <= *point {
+            if *point < mid.end || (!left.is_empty() && contains(left, point)) {
+                return true;
+            }
+
+            if right.is_empty() {
+                return false;
+            }
+
+            nodes = right;
+        } else if left.is_empty() {
+            return false;
+        } else {
+            nodes = left;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::mem::size_of;
+
+    use proptest::{collection::vec, test_runner::TestRunner};
+
+    #[test]
+    fn nodes_do_not_store_values() {
+        assert_eq!(size_of::<Node<u32, ()>>(), 3 * size_of::<u32>());
+    }
+
+    #[test]
+    fn contains_random() {
+        const DOM: Range<i32> = -1000..1000;
+        const LEN: usize = 1000_usize;
+
+        TestRunner::default()
+            .run(
+                &(vec(DOM, LEN), vec(DOM, LEN), DOM),
+                |(start, end, point)| {
+                    let intervals = start
+                        .iter()
+                        .zip(&end)
+                        .map(|(&start, &end)| start..end)
+                        .collect::<Vec<_>>();
+
+                    let set = ITreeSet::<_>::from_intervals(intervals.iter().cloned());
+
+                    assert_eq!(
+                        set.contains(&point),
+                        intervals.iter().any(|interval| interval.contains(&point))
+                    );
+
+                    assert_eq!(
+                        set.overlaps(point..point + 10),
+                        intervals
+                            .iter()
+                            .any(|interval| interval.start < point + 10 && point < interval.end)
+                    );
+
+                    Ok(())
+                },
+            )
+            .unwrap()
+    }
+}
@@ -0,0 +1,180 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::ops::{ControlFlow, Range};
+
+#[cfg(feature = "rayon")]
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+
+use crate::{Entry, ITree, Item, Node};
+
+/// Collection of interval trees each holding the items whose interval starts fall into a given range
+///
+/// Avoids a single contiguous allocation and a global sort for very large datasets while queries are fanned out only to the shards which can contain overlapping intervals.
+#[derive(Debug, Clone)]
+pub struct ITreeShards<K, V, S = Box<[Node<K, V>]>> {
+    bounds: Box<[K]>,
+    shards: Box<[ITree<K, V, S>]>,
+}
+
+impl<K, V, S> ITreeShards<K, V, S>
+where
+    K: Ord + Clone,
+    S: AsMut<[Node<K, V>]> + FromIterator<Node<K, V>>,
+{
+    /// Construct new shards by distributing the given `items` according to their interval starts
+    ///
+    /// The `bounds` split the keys into one more shards than there are bounds, the first shard containing all items starting before the first bound and the last one all items starting at or after the last bound.
+    ///
+    /// # Panics
+    ///
+    /// If the `bounds` are not strictly increasing.
+    pub fn new<B, I>(bounds: B, items: I) -> Self
+    where
+        B: IntoIterator<Item = K>,
+        I: IntoIterator<Item = Item<K, V>>,
+    {
+        let bounds = bounds.into_iter().collect::<Box<[_]>>();
+
+        assert!(
+            bounds.windows(2).all(|bounds| bounds[0] < bounds[1]),
+            "bounds must be strictly increasing"
+        );
+
+        let mut items_by_shard = (0..=bounds.len()).map(|_| Vec::new()).collect::<Vec<_>>();
+
+        for item in items {
+            let shard = bounds.partition_point(|bound| *bound <= item.0.start);
+
+            items_by_shard[shard].push(item);
+        }
+
+        Self {
+            bounds,
+            shards: items_by_shard.into_iter().map(ITree::new).collect(),
+        }
+    }
+}
+
+impl<K, V, S> ITreeShards<K, V, S>
+where
+    S: AsRef<[Node<K, V>]>,
+{
+    /// The number of items in all shards
+    pub fn len(&self) -> usize {
+        self.shards.iter().map(|shard| shard.len()).sum()
+    }
+
+    /// Whether all shards are empty
+    pub fn is_empty(&self) -> bool {
+        self.shards.iter().all(|shard| shard.is_empty())
+    }
+
+    /// The bounds between the shards
+    pub fn bounds(&self) -> &[K] {
+        &self.bounds
+    }
+
+    /// The individual trees in the order of their ranges of interval starts
+    pub fn shards(&self) -> &[ITree<K, V, S>] {
+        &self.shards
+    }
+
+    /// Iterate over all items in the order of their interval starts
+    pub fn iter(&self) -> impl Iterator<Item = &Node<K, V>> {
+        self.shards.iter().flat_map(|shard| shard.iter())
+    }
+
+    /// Query all shards which can contain intervals overlapping the given interval
+    pub fn query<'a, H>(&'a self, interval: Range<K>, mut handler: H) -> ControlFlow<()>
+    where
+        K: Ord + Clone,
+        H: FnMut(Entry<'a, K, V>) -> ControlFlow<()>,
+    {
+        for shard in self.relevant_shards(&interval) {
+            shard.query(interval.clone(), &mut handler)?;
+        }
+
+        ControlFlow::Continue(())
+    }
+
+    /// Query all shards which can contain intervals overlapping the given interval, in parallel
+    #[cfg(feature = "rayon")]
+    pub fn par_query<'a, H>(&'a self, interval: Range<K>, handler: H) -> ControlFlow<()>
+    where
+        K: Ord + Clone + Send + Sync,
+        V: Sync,
+        S: Sync,
+        H: Fn(Entry<'a, K, V>) -> ControlFlow<()> + Sync,
+    {
+        self.relevant_shards(&interval)
+            .par_iter()
+            .try_for_each(|shard| shard.par_query(interval.clone(), &handler))
+    }
+
+    fn relevant_shards(&self, interval: &Range<K>) -> &[ITree<K, V, S>]
+    where
+        K: Ord,
+    {
+        let len = self.bounds.partition_point(|bound| *bound < interval.end);
+
+        &self.shards[..=len]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use proptest::{collection::vec, test_runner::TestRunner};
+
+    #[test]
+    fn query_random() {
+        const DOM: Range<i32> = -1000..1000;
+        const LEN: usize = 1000_usize;
+
+        TestRunner::default()
+            .run(
+                &(vec(DOM, LEN), vec(DOM, LEN), vec(DOM, 0..8), DOM, DOM),
+                |(start, end, mut bounds, query_start, query_end)| {
+                    bounds.sort_unstable();
+                    bounds.dedup();
+
+                    let items = start
+                        .iter()
+                        .zip(&end)
+                        .enumerate()
+                        .map(|(index, (&start, &end))| (start..end, index))
+                        .collect::<Vec<_>>();
+
+                    let tree = ITree::<_, _>::new(items.iter().cloned());
+
+                    let mut result1 = Vec::new();
+                    let _ = tree.query(query_start..query_end, |entry| {
+                        result1.push(*entry.value());
+                        ControlFlow::Continue(())
+                    });
+
+                    let shards = ITreeShards::<_, _>::new(bounds.iter().copied(), items);
+                    assert_eq!(shards.len(), LEN);
+                    assert_eq!(shards.shards().len(), bounds.len() + 1);
+                    assert!(shards
+                        .iter()
+                        .map(Node::interval)
+                        .eq(tree.iter().map(Node::interval)));
+
+                    let mut result2 = Vec::new();
+                    let _ = shards.query(query_start..query_end, |entry| {
+                        result2.push(*entry.value());
+                        ControlFlow::Continue(())
+                    });
+
+                    result1.sort_unstable();
+                    result2.sort_unstable();
+                    assert_eq!(result1, result2);
+
+                    Ok(())
+                },
+            )
+            .unwrap()
+    }
+}
@@ -0,0 +1,63 @@
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use arc_swap::ArcSwap;
+
+use crate::{ITree, Node};
+
+/// Publishes trees to concurrent readers which obtain cheap snapshots
+///
+/// Requires the `arc-swap` feature. Loading a snapshot does not block publishers and only increments a reference count.
+#[derive(Debug)]
+pub struct SharedITree<K, V> {
+    current: ArcSwap<Snapshot<K, V>>,
+}
+
+type Snapshot<K, V> = ITree<K, V, Arc<[Node<K, V>]>>;
+
+impl<K, V> SharedITree<K, V> {
+    /// Start publishing the given `tree`
+    pub fn new<S>(tree: ITree<K, V, S>) -> Self
+    where
+        S: Into<Arc<[Node<K, V>]>>,
+    {
+        Self {
+            current: ArcSwap::from_pointee(tree.into_shared()),
+        }
+    }
+
+    /// A snapshot of the current tree
+    pub fn load(&self) -> ITree<K, V, Arc<[Node<K, V>]>> {
+        let current = self.current.load();
+
+        ITree {
+            nodes: current.nodes.clone(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Replace the current tree, leaving existing snapshots unaffected
+    pub fn store<S>(&self, tree: ITree<K, V, S>)
+    where
+        S: Into<Arc<[Node<K, V>]>>,
+    {
+        self.current.store(Arc::new(tree.into_shared()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshots_survive_store() {
+        let shared = SharedITree::new(ITree::<_, _>::new([(0..1, 0)]));
+
+        let snapshot = shared.load();
+
+        shared.store(ITree::<_, _, Vec<_>>::new([(0..1, 0), (1..2, 1)]));
+
+        assert_eq!(snapshot.len(), 1);
+        assert_eq!(shared.load().len(), 2);
+    }
+}
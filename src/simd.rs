@@ -0,0 +1,284 @@
+use core::ops::{ControlFlow, Range};
+
+use crate::query::traverse_bucketed;
+use crate::soa::Columns;
+use crate::{Entry, ITreeSoA, PrimitiveKey};
+
+const LANES: usize = 8;
+
+/// Interval bounds whose comparisons can be vectorized
+///
+/// Requires the `simd` feature. This trait is sealed and implemented for the primitive integer types. On x86-64, the bounds of type `i32` and `u32` are compared using SSE2 instructions whereas all other types fall back to scalar comparisons.
+pub trait SimdKey: PrimitiveKey + sealed::Lanes {}
+
+mod sealed {
+    use super::LANES;
+
+    pub trait Lanes: Sized {
+        fn lane_mask(starts: &[Self; LANES], ends: &[Self; LANES], start: Self, end: Self) -> u32;
+    }
+}
+
+macro_rules! impl_simd_key {
+    ($($ty:ty),*) => {
+        $(
+            impl SimdKey for $ty {}
+        )*
+    };
+}
+
+impl_simd_key!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+macro_rules! impl_scalar_lanes {
+    ($($ty:ty),*) => {
+        $(
+            impl sealed::Lanes for $ty {
+                fn lane_mask(starts: &[Self; LANES], ends: &[Self; LANES], start: Self, end: Self) -> u32 {
+                    scalar_lane_mask(starts, ends, start, end)
+                }
+            }
+        )*
+    };
+}
+
+impl_scalar_lanes!(u8, u16, u64, usize, i8, i16, i64, isize);
+
+#[cfg(not(all(target_arch = "x86_64", target_feature = "sse2")))]
+impl_scalar_lanes!(u32, i32);
+
+#[cfg(all(target_arch = "x86_64", target_feature = "sse2"))]
+impl sealed::Lanes for i32 {
+    fn lane_mask(starts: &[Self; LANES], ends: &[Self; LANES], start: Self, end: Self) -> u32 {
+        #[allow(unsafe_code)]
+        // SAFETY: The `sse2` target feature is enabled at compile time.
+        unsafe {
+            sse2::lane_mask(starts, ends, start, end, 0)
+        }
+    }
+}
+
+#[cfg(all(target_arch = "x86_64", target_feature = "sse2"))]
+impl sealed::Lanes for u32 {
+    fn lane_mask(starts: &[Self; LANES], ends: &[Self; LANES], start: Self, end: Self) -> u32 {
+        #[allow(unsafe_code)]
+        // SAFETY: The `sse2` target feature is enabled at compile time.
+        unsafe {
+            sse2::lane_mask(
+                &starts.map(|start| start as i32),
+                &ends.map(|end| end as i32),
+                start as i32,
+                end as i32,
+                i32::MIN,
+            )
+        }
+    }
+}
+
+#[cfg(all(target_arch = "x86_64", target_feature = "sse2"))]
+mod sse2 {
+    use core::arch::x86_64::{
+        __m128i, _mm_and_si128, _mm_castsi128_ps, _mm_cmpgt_epi32, _mm_cmplt_epi32,
+        _mm_loadu_si128, _mm_movemask_ps, _mm_set1_epi32, _mm_xor_si128,
+    };
+
+    use super::LANES;
+
+    /// Compares the bounds after flipping the bits in `bias`, so that `i32::MIN` orders unsigned integers stored as signed ones
+    #[allow(unsafe_code)]
+    #[target_feature(enable = "sse2")]
+    pub(super) unsafe fn lane_mask(
+        starts: &[i32; LANES],
+        ends: &[i32; LANES],
+        start: i32,
+        end: i32,
+        bias: i32,
+    ) -> u32 {
+        let bias = _mm_set1_epi32(bias);
+        let start = _mm_xor_si128(_mm_set1_epi32(start), bias);
+        let end = _mm_xor_si128(_mm_set1_epi32(end), bias);
+
+        let mut mask = 0;
+
+        for half in [0, LANES / 2] {
+            // SAFETY: Both arrays contain `LANES` elements, so four elements can be read starting at either half.
+            let (lane_starts, lane_ends) = unsafe {
+                (
+                    _mm_loadu_si128(starts[half..].as_ptr().cast::<__m128i>()),
+                    _mm_loadu_si128(ends[half..].as_ptr().cast::<__m128i>()),
+                )
+            };
+
+            let hits = _mm_and_si128(
+                _mm_cmplt_epi32(_mm_xor_si128(lane_starts, bias), end),
+                _mm_cmpgt_epi32(_mm_xor_si128(lane_ends, bias), start),
+            );
+
+            mask |= (_mm_movemask_ps(_mm_castsi128_ps(hits)) as u32) << half;
+        }
+
+        mask
+    }
+}
+
+fn scalar_lane_mask<K>(starts: &[K; LANES], ends: &[K; LANES], start: K, end: K) -> u32
+where
+    K: PrimitiveKey,
+{
+    let mut mask = 0;
+
+    for index in 0..LANES {
+        mask |= u32::from((starts[index] < end) & (start < ends[index])) << index;
+    }
+
+    mask
+}
+
+impl<K, V> ITreeSoA<K, V>
+where
+    K: SimdKey,
+{
+    /// Query for all intervals overlapping the given interval, scanning subtrees of at most `bucket_len` nodes in lanes of eight nodes at once
+    ///
+    /// Requires the `simd` feature. As the starts and ends of a subtree are stored contiguously, each lane is loaded into vector registers and compared at once for keys supported by [`SimdKey`]. Yields the same items as [`query`][Self::query].
+    pub fn query_simd<'a, H>(
+        &'a self,
+        interval: Range<K>,
+        bucket_len: usize,
+        mut handler: H,
+    ) -> ControlFlow<()>
+    where
+        H: FnMut(Entry<'a, K, V>) -> ControlFlow<()>,
+    {
+        traverse_bucketed(
+            &interval,
+            self.columns(),
+            bucket_len,
+            &mut |(start, end, _max, value)| handler(Entry::new(start, end, value)),
+            &mut |columns, handler| scan_lanes(&interval, handler, columns),
+        )
+    }
+}
+
+fn scan_lanes<'a, K, V, H>(
+    interval: &Range<K>,
+    handler: &mut H,
+    columns: Columns<'a, K, V>,
+) -> ControlFlow<()>
+where
+    K: SimdKey,
+    H: FnMut((&'a K, &'a K, &'a K, &'a V)) -> ControlFlow<()>,
+{
+    let Range { start, end } = *interval;
+
+    let mut emit = |index: usize| {
+        handler((
+            &columns.starts[index],
+            &columns.ends[index],
+            &columns.maxs[index],
+            &columns.values[index],
+        ))
+    };
+
+    let lanes = columns.starts.len() / LANES;
+
+    for lane in 0..lanes {
+        let offset = lane * LANES;
+
+        let starts: &[K; LANES] = columns.starts[offset..offset + LANES].try_into().unwrap();
+        let ends: &[K; LANES] = columns.ends[offset..offset + LANES].try_into().unwrap();
+
+        if end <= starts[0] {
+            return ControlFlow::Continue(());
+        }
+
+        let mut mask = K::lane_mask(starts, ends, start, end);
+
+        while mask != 0 {
+            let index = mask.trailing_zeros() as usize;
+            mask &= mask - 1;
+
+            emit(offset + index)?;
+        }
+    }
+
+    for index in lanes * LANES..columns.starts.len() {
+        if end <= columns.starts[index] {
+            break;
+        }
+
+        if start < columns.ends[index] {
+            emit(index)?;
+        }
+    }
+
+    ControlFlow::Continue(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use proptest::{collection::vec, test_runner::TestRunner};
+
+    use crate::ITree;
+
+    #[test]
+    fn query_simd_random() {
+        const DOM: Range<i32> = -1000..1000;
+        const LEN: usize = 1000_usize;
+
+        TestRunner::default()
+            .run(
+                &(vec(DOM, LEN), vec(DOM, LEN), DOM, DOM, 0_usize..100),
+                |(start, end, query_start, query_end, bucket_len)| {
+                    let tree = ITree::<_, _>::new(
+                        start
+                            .iter()
+                            .zip(&end)
+                            .enumerate()
+                            .map(|(index, (&start, &end))| (start..end, index)),
+                    );
+
+                    let mut result1 = Vec::new();
+                    let _ = tree.query(query_start..query_end, |entry| {
+                        result1.push(*entry.value());
+                        ControlFlow::Continue(())
+                    });
+
+                    let tree = ITreeSoA::from(tree);
+
+                    let mut result2 = Vec::new();
+                    let _ = tree.query_simd(query_start..query_end, bucket_len, |entry| {
+                        result2.push(*entry.value());
+                        ControlFlow::Continue(())
+                    });
+
+                    result1.sort_unstable();
+                    result2.sort_unstable();
+                    assert_eq!(result1, result2);
+
+                    Ok(())
+                },
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn query_simd_unsigned() {
+        let tree = ITreeSoA::from(ITree::<_, _>::new((0..100_u32).map(|start| {
+            (
+                start * 0x0200_0000..start * 0x0200_0000 + 0x0300_0000,
+                start,
+            )
+        })));
+
+        let mut result = Vec::new();
+        let _ = tree.query_simd(0x8000_0000..0x8400_0000, 64, |entry| {
+            result.push(*entry.value());
+            ControlFlow::Continue(())
+        });
+
+        result.sort_unstable();
+        assert_eq!(result, [63, 64, 65]);
+    }
+}
@@ -0,0 +1,155 @@
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::mem::{size_of, size_of_val};
+use core::ops::Range;
+
+use crate::{ITree, Node};
+
+/// The number of bytes owned by a key or value on the heap, not counting its inline size
+///
+/// Implemented for the primitive types, which own no heap memory, and for the common owning containers of the standard library.
+pub trait HeapSize {
+    /// The number of heap bytes transitively owned by `self`
+    fn heap_size(&self) -> usize;
+}
+
+macro_rules! impl_heap_size {
+    ($($type:ty),*) => {
+        $(
+            impl HeapSize for $type {
+                fn heap_size(&self) -> usize {
+                    0
+                }
+            }
+        )*
+    };
+}
+
+impl_heap_size!(
+    (),
+    bool,
+    char,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64,
+    &str
+);
+
+impl HeapSize for String {
+    fn heap_size(&self) -> usize {
+        self.capacity()
+    }
+}
+
+impl HeapSize for Box<str> {
+    fn heap_size(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> HeapSize for Box<T>
+where
+    T: HeapSize,
+{
+    fn heap_size(&self) -> usize {
+        size_of::<T>() + (**self).heap_size()
+    }
+}
+
+impl<T> HeapSize for Box<[T]>
+where
+    T: HeapSize,
+{
+    fn heap_size(&self) -> usize {
+        size_of_val::<[T]>(self) + self.iter().map(T::heap_size).sum::<usize>()
+    }
+}
+
+impl<T> HeapSize for Vec<T>
+where
+    T: HeapSize,
+{
+    fn heap_size(&self) -> usize {
+        self.capacity() * size_of::<T>() + self.iter().map(T::heap_size).sum::<usize>()
+    }
+}
+
+impl<T> HeapSize for Option<T>
+where
+    T: HeapSize,
+{
+    fn heap_size(&self) -> usize {
+        self.as_ref().map_or(0, T::heap_size)
+    }
+}
+
+impl<T> HeapSize for Range<T>
+where
+    T: HeapSize,
+{
+    fn heap_size(&self) -> usize {
+        self.start.heap_size() + self.end.heap_size()
+    }
+}
+
+impl<K, V, S> ITree<K, V, S>
+where
+    S: AsRef<[Node<K, V>]>,
+{
+    /// The number of bytes occupied by the node array
+    ///
+    /// Heap memory owned by the keys and values is not included. It is counted also if the nodes are not stored on the heap, e.g. when they are backed by a memory map.
+    pub fn heap_size(&self) -> usize {
+        size_of_val(self.nodes.as_ref())
+    }
+
+    /// The number of bytes occupied by the node array and owned by its keys and values
+    pub fn deep_heap_size(&self) -> usize
+    where
+        K: HeapSize,
+        V: HeapSize,
+    {
+        self.heap_size()
+            + self
+                .nodes
+                .as_ref()
+                .iter()
+                .map(|node| {
+                    node.start.heap_size()
+                        + node.end.heap_size()
+                        + node.max.heap_size()
+                        + node.value.heap_size()
+                })
+                .sum::<usize>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn heap_size_of_nodes_and_values() {
+        let tree =
+            ITree::<u32, String>::new([(0..1, String::with_capacity(10)), (1..2, String::new())]);
+
+        assert_eq!(tree.heap_size(), 2 * size_of::<Node<u32, String>>());
+        assert_eq!(tree.deep_heap_size(), tree.heap_size() + 10);
+
+        let tree = ITree::<u32, Vec<Box<u64>>>::new([(0..1, vec![Box::new(1), Box::new(2)])]);
+
+        assert_eq!(tree.deep_heap_size(), tree.heap_size() + 2 * 8 + 2 * 8);
+    }
+}
@@ -0,0 +1,33 @@
+use smallvec::SmallVec;
+
+use crate::{ITree, Node};
+
+/// Interval tree storing up to `N` nodes inline without allocating
+///
+/// Requires the `smallvec` feature. Construct it like any other tree, e.g. using [`ITree::new`] or [`ITree::from_sorted`], and it will spill onto the heap only if it contains more than `N` items.
+pub type SmallITree<K, V, const N: usize> = ITree<K, V, SmallVec<[Node<K, V>; N]>>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::ops::ControlFlow;
+
+    #[test]
+    fn small_trees_are_stored_inline() {
+        let tree = SmallITree::<_, _, 4>::new([(2..3, 2), (0..1, 0), (1..2, 1)]);
+        assert!(!tree.as_inner().spilled());
+        assert!(tree.is_valid());
+
+        let mut result = Vec::new();
+        let _ = tree.query(1..3, |entry| {
+            result.push(*entry.value());
+            ControlFlow::Continue(())
+        });
+        result.sort_unstable();
+        assert_eq!(result, [1, 2]);
+
+        let tree = SmallITree::<_, _, 2>::new((0..3).map(|start| (start..start + 1, ())));
+        assert!(tree.as_inner().spilled());
+    }
+}
@@ -0,0 +1,374 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::ops::{ControlFlow, Range};
+
+#[cfg(feature = "serde")]
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize};
+
+use crate::query::{split, traverse, Subtree};
+use crate::validate::validate_with;
+use crate::{Entry, ITree, InvalidTree, Item, Node};
+
+/// Interval tree storing the starts, ends, maxima and values of its nodes in separate arrays
+///
+/// Queries descend through the starts and maxima only, so that large values do not occupy the cache lines touched by the traversal.
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct ITreeSoA<K, V> {
+    starts: Box<[K]>,
+    ends: Box<[K]>,
+    maxs: Box<[K]>,
+    values: Box<[V]>,
+}
+
+impl<K, V> ITreeSoA<K, V>
+where
+    K: Ord + Clone,
+{
+    /// Construct a new tree by sorting the given `items`
+    pub fn new<I>(items: I) -> Self
+    where
+        I: IntoIterator<Item = Item<K, V>>,
+    {
+        ITree::<K, V, Vec<Node<K, V>>>::new(items).into()
+    }
+}
+
+impl<K, V> ITreeSoA<K, V> {
+    /// The number of items in the tree
+    pub fn len(&self) -> usize {
+        self.starts.len()
+    }
+
+    /// Whether the tree contains no items
+    pub fn is_empty(&self) -> bool {
+        self.starts.is_empty()
+    }
+
+    /// Iterate over all items in the order of their interval starts
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = Entry<'_, K, V>> + ExactSizeIterator {
+        self.starts
+            .iter()
+            .zip(&*self.ends)
+            .zip(&*self.values)
+            .map(|((start, end), value)| Entry::new(start, end, value))
+    }
+
+    /// Query for all intervals overlapping the given interval
+    pub fn query<'a, H>(&'a self, interval: Range<K>, mut handler: H) -> ControlFlow<()>
+    where
+        K: Ord,
+        H: FnMut(Entry<'a, K, V>) -> ControlFlow<()>,
+    {
+        traverse(&interval, self.columns(), &mut |(
+            start,
+            end,
+            _max,
+            value,
+        )| {
+            handler(Entry::new(start, end, value))
+        })
+    }
+
+    pub(crate) fn columns(&self) -> Columns<'_, K, V> {
+        Columns {
+            starts: &self.starts,
+            ends: &self.ends,
+            maxs: &self.maxs,
+            values: &self.values,
+        }
+    }
+}
+
+impl<K, V> ITreeSoA<K, V> {
+    /// The interval starts in the order of the nodes
+    pub fn starts(&self) -> &[K] {
+        &self.starts
+    }
+
+    /// The interval ends in the order of the nodes
+    pub fn ends(&self) -> &[K] {
+        &self.ends
+    }
+
+    /// The maxima of the interval upper bounds of the subtrees in the order of the nodes
+    pub fn max_ends(&self) -> &[K] {
+        &self.maxs
+    }
+
+    /// The values in the order of the nodes
+    pub fn values(&self) -> &[V] {
+        &self.values
+    }
+
+    /// Move out the columns of starts, ends, maxima and values, e.g. to hand them to Arrow without copying
+    #[allow(clippy::type_complexity)]
+    pub fn into_parts(self) -> (Box<[K]>, Box<[K]>, Box<[K]>, Box<[V]>) {
+        (self.starts, self.ends, self.maxs, self.values)
+    }
+}
+
+impl<K, V> ITreeSoA<K, V>
+where
+    K: Ord,
+{
+    /// Reassemble a tree from columns returned by [`into_parts`][Self::into_parts] after verifying that they are organized as an interval tree
+    ///
+    /// # Panics
+    ///
+    /// If the columns do not have the same length.
+    pub fn from_parts(
+        starts: Box<[K]>,
+        ends: Box<[K]>,
+        maxs: Box<[K]>,
+        values: Box<[V]>,
+    ) -> Result<Self, InvalidTree> {
+        assert!(
+            starts.len() == ends.len()
+                && starts.len() == maxs.len()
+                && starts.len() == values.len(),
+            "columns must have the same length"
+        );
+
+        validate_with(starts.len(), |index| {
+            (&starts[index], &ends[index], &maxs[index])
+        })?;
+
+        Ok(Self {
+            starts,
+            ends,
+            maxs,
+            values,
+        })
+    }
+}
+
+/// Rejects columns of different lengths and [checks][ITreeSoA::from_parts] that they are organized as an interval tree
+#[cfg(feature = "serde")]
+impl<'de, K, V> Deserialize<'de> for ITreeSoA<K, V>
+where
+    K: Ord + Deserialize<'de>,
+    V: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let Parts {
+            starts,
+            ends,
+            maxs,
+            values,
+        } = Parts::deserialize(deserializer)?;
+
+        if starts.len() != ends.len() || starts.len() != maxs.len() || starts.len() != values.len()
+        {
+            return Err(D::Error::custom("columns must have the same length"));
+        }
+
+        Self::from_parts(starts, ends, maxs, values).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+#[serde(rename = "ITreeSoA")]
+struct Parts<K, V> {
+    starts: Box<[K]>,
+    ends: Box<[K]>,
+    maxs: Box<[K]>,
+    values: Box<[V]>,
+}
+
+impl<K, V, S> From<ITree<K, V, S>> for ITreeSoA<K, V>
+where
+    S: Into<Vec<Node<K, V>>>,
+{
+    fn from(tree: ITree<K, V, S>) -> Self {
+        let nodes: Vec<_> = tree.nodes.into();
+
+        let mut starts = Vec::with_capacity(nodes.len());
+        let mut ends = Vec::with_capacity(nodes.len());
+        let mut maxs = Vec::with_capacity(nodes.len());
+        let mut values = Vec::with_capacity(nodes.len());
+
+        for node in nodes {
+            starts.push(node.start);
+            ends.push(node.end);
+            maxs.push(node.max);
+            values.push(node.value);
+        }
+
+        Self {
+            starts: starts.into(),
+            ends: ends.into(),
+            maxs: maxs.into(),
+            values: values.into(),
+        }
+    }
+}
+
+pub(crate) struct Columns<'a, K, V> {
+    pub(crate) starts: &'a [K],
+    pub(crate) ends: &'a [K],
+    pub(crate) maxs: &'a [K],
+    pub(crate) values: &'a [V],
+}
+
+impl<K, V> Clone for Columns<'_, K, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K, V> Copy for Columns<'_, K, V> {}
+
+impl<'a, K, V> Subtree for Columns<'a, K, V>
+where
+    K: Ord,
+{
+    type Key = K;
+    type Bound = &'a K;
+    type Root = (&'a K, &'a K, &'a K, &'a V);
+
+    fn len(self) -> usize {
+        self.starts.len()
+    }
+
+    fn split(self) -> Option<(Self, Self::Root, Self)> {
+        let (starts_left, start, starts_right) = split(self.starts)?;
+        let (ends_left, end, ends_right) = split(self.ends)?;
+        let (maxs_left, max, maxs_right) = split(self.maxs)?;
+        let (values_left, value, values_right) = split(self.values)?;
+
+        let left = Self {
+            starts: starts_left,
+            ends: ends_left,
+            maxs: maxs_left,
+            values: values_left,
+        };
+
+        let right = Self {
+            starts: starts_right,
+            ends: ends_right,
+            maxs: maxs_right,
+            values: values_right,
+        };
+
+        Some((left, (start, end, max, value), right))
+    }
+
+    fn start(root: Self::Root) -> Self::Bound {
+        root.0
+    }
+
+    fn end(root: Self::Root) -> Self::Bound {
+        root.1
+    }
+
+    fn max(root: Self::Root) -> Self::Bound {
+        root.2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use proptest::{collection::vec, test_runner::TestRunner};
+
+    #[test]
+    fn query_random() {
+        const DOM: Range<i32> = -1000..1000;
+        const LEN: usize = 1000_usize;
+
+        TestRunner::default()
+            .run(
+                &(vec(DOM, LEN), vec(DOM, LEN), DOM, DOM),
+                |(start, end, query_start, query_end)| {
+                    let tree = ITree::<_, _>::new(
+                        start
+                            .iter()
+                            .zip(&end)
+                            .enumerate()
+                            .map(|(index, (&start, &end))| (start..end, index)),
+                    );
+
+                    let mut result1 = Vec::new();
+                    let _ = tree.query(query_start..query_end, |entry| {
+                        result1.push(*entry.value());
+                        ControlFlow::Continue(())
+                    });
+
+                    let tree = ITreeSoA::from(tree);
+
+                    let mut result2 = Vec::new();
+                    let _ = tree.query(query_start..query_end, |entry| {
+                        result2.push(*entry.value());
+                        ControlFlow::Continue(())
+                    });
+
+                    assert_eq!(result1, result2);
+
+                    let (starts, ends, maxs, values) = tree.into_parts();
+                    let tree = ITreeSoA::from_parts(starts, ends, maxs, values).unwrap();
+                    assert_eq!(tree.len(), LEN);
+
+                    Ok(())
+                },
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn from_parts_rejects_invalid_trees() {
+        let tree = ITreeSoA::new([(0..5, 'a'), (1..2, 'b'), (2..3, 'c')]);
+        assert_eq!(tree.starts(), [0, 1, 2]);
+        assert_eq!(tree.ends(), [5, 2, 3]);
+        assert_eq!(tree.max_ends(), [5, 5, 3]);
+        assert_eq!(tree.values(), ['a', 'b', 'c']);
+
+        let (starts, ends, mut maxs, values) = tree.clone().into_parts();
+        maxs[2] = 2;
+        assert_eq!(
+            ITreeSoA::from_parts(starts, ends, maxs, values).unwrap_err(),
+            InvalidTree::InvalidMax(2)
+        );
+
+        let (mut starts, ends, maxs, values) = tree.into_parts();
+        starts.swap(0, 1);
+        assert_eq!(
+            ITreeSoA::from_parts(starts, ends, maxs, values).unwrap_err(),
+            InvalidTree::Unsorted(1)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_rejects_invalid_trees() {
+        let tree = ITreeSoA::new([(0..5, 'a'), (1..2, 'b'), (2..3, 'c')]);
+
+        let json = serde_json::to_string(&tree).unwrap();
+        let other = serde_json::from_str::<ITreeSoA<i32, char>>(&json).unwrap();
+        assert!(other
+            .iter()
+            .map(|entry| entry.interval())
+            .eq(tree.iter().map(|entry| entry.interval())));
+
+        let err = serde_json::from_str::<ITreeSoA<i32, char>>(
+            r#"{"starts":[0,1,2],"ends":[5,2,3],"maxs":[5,5],"values":["a","b","c"]}"#,
+        )
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("columns must have the same length"));
+
+        let err = serde_json::from_str::<ITreeSoA<i32, char>>(
+            r#"{"starts":[0,1,2],"ends":[5,2,3],"maxs":[5,5,2],"values":["a","b","c"]}"#,
+        )
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("node 2 has an invalid maximum end"));
+    }
+}
@@ -1,7 +1,19 @@
-use std::marker::PhantomData;
+use alloc::vec::Vec;
+use core::cmp::{Ordering, Reverse};
+use core::error::Error;
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::Range;
+#[cfg(feature = "threads")]
+use std::thread::scope;
 
 #[cfg(feature = "rayon")]
-use rayon::{join, slice::ParallelSliceMut};
+use rayon::{
+    iter::{FromParallelIterator, IntoParallelIterator, ParallelIterator},
+    join,
+    slice::ParallelSliceMut,
+    ThreadPool,
+};
 
 use crate::{ITree, Item, Node};
 
@@ -11,22 +23,152 @@ where
     S: AsMut<[Node<K, V>]> + FromIterator<Node<K, V>>,
 {
     /// Construct a new tree by sorting the given `items`
+    ///
+    /// Items are ordered by ascending interval start and then by descending interval end. This makes the resulting layout independent of the order of the input except for items with identical intervals, and keeps the maximum of the interval upper bounds tight in right subtrees when many intervals share a start.
+    ///
+    /// Input which is already sorted or reverse-sorted is detected by a linear pre-pass and not sorted again.
+    ///
+    /// Constructors require `K: Clone` because every node stores its own copy of the maximum of its subtree. Each interval end is cloned once and once more for every node whose maximum it replaces.
     pub fn new<I>(items: I) -> Self
     where
         I: IntoIterator<Item = Item<K, V>>,
     {
-        let mut nodes = items
+        Self::sort_nodes(items.into_iter().map(node).collect())
+    }
+
+    /// Construct a new tree by sorting the given `items`, rejecting empty and inverted intervals
+    ///
+    /// Intervals with `start >= end` never overlap any query and usually indicate malformed input. Use [`new_non_empty`][Self::new_non_empty] to skip them instead.
+    pub fn new_strict<I>(items: I) -> Result<Self, EmptyInterval>
+    where
+        I: IntoIterator<Item = Item<K, V>>,
+    {
+        Self::try_new(items.into_iter().enumerate().map(|(index, item)| {
+            if item.0.start < item.0.end {
+                Ok(item)
+            } else {
+                Err(EmptyInterval(index))
+            }
+        }))
+    }
+
+    /// Construct a new tree by sorting the given `items`, skipping empty and inverted intervals
+    pub fn new_non_empty<I>(items: I) -> Self
+    where
+        I: IntoIterator<Item = Item<K, V>>,
+    {
+        Self::new(
+            items
+                .into_iter()
+                .filter(|(interval, _value)| interval.start < interval.end),
+        )
+    }
+
+    /// Construct a new tree by sorting the given `items` using cached sort keys
+    ///
+    /// The `key` function is called twice per item and its results are used instead of comparing `K` directly, which is beneficial if comparing `K` is expensive. It must preserve the ordering of `K`, i.e. `key(lhs).cmp(&key(rhs))` must equal `lhs.cmp(rhs)`.
+    ///
+    /// Orders items like [`new`][Self::new].
+    pub fn new_by_cached_key<I, F, C>(items: I, mut key: F) -> Self
+    where
+        I: IntoIterator<Item = Item<K, V>>,
+        F: FnMut(&K) -> C,
+        C: Ord,
+    {
+        let mut nodes = items.into_iter().map(node).collect::<S>();
+
+        {
+            let nodes = nodes.as_mut();
+
+            nodes.sort_by_cached_key(|node| (key(&node.start), Reverse(key(&node.end))));
+
+            if !nodes.is_empty() {
+                update_max(nodes);
+            }
+        }
+
+        Self {
+            nodes,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Construct a new tree by sorting the given `items` and merging items with identical intervals
+    ///
+    /// The values of items with identical intervals are merged using `combine` in the order in which they were yielded by `items`.
+    pub fn new_dedup<I, F>(items: I, mut combine: F) -> Self
+    where
+        I: IntoIterator<Item = Item<K, V>>,
+        F: FnMut(V, V) -> V,
+    {
+        let mut items = items.into_iter().map(node).collect::<Vec<_>>();
+
+        items.sort_by(cmp);
+
+        let mut nodes = Vec::<Node<K, V>>::with_capacity(items.len());
+
+        for node in items {
+            match nodes.last() {
+                Some(last) if last.start == node.start && last.end == node.end => {
+                    let mut last = nodes.pop().unwrap();
+
+                    last.value = combine(last.value, node.value);
+
+                    nodes.push(last);
+                }
+                _ => nodes.push(node),
+            }
+        }
+
+        let mut nodes = nodes.into_iter().collect::<S>();
+
+        {
+            let nodes = nodes.as_mut();
+
+            if !nodes.is_empty() {
+                update_max(nodes);
+            }
+        }
+
+        Self {
+            nodes,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Construct a new tree by sorting the given fallible `items`
+    ///
+    /// Stops at and returns the first error produced by `items`.
+    pub fn try_new<I, E>(items: I) -> Result<Self, E>
+    where
+        I: IntoIterator<Item = Result<Item<K, V>, E>>,
+    {
+        let nodes = items
             .into_iter()
-            .map(|(interval, value)| {
-                let end = interval.end.clone();
-                ((interval, value), end)
-            })
-            .collect::<S>();
+            .map(|item| item.map(node))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self::sort_nodes(nodes))
+    }
+
+    /// Construct a new tree from the given `items` which are already sorted by interval start
+    ///
+    /// Supplying `items` which are not sorted is safe but will lead to incorrect results. This is checked only if debug assertions are enabled.
+    pub fn from_sorted<I>(items: I) -> Self
+    where
+        I: IntoIterator<Item = Item<K, V>>,
+    {
+        let mut nodes = items.into_iter().map(node).collect::<S>();
 
         {
             let nodes = nodes.as_mut();
 
-            nodes.sort_unstable_by(|lhs, rhs| (lhs.0).0.start.cmp(&(rhs.0).0.start));
+            debug_assert!(
+                nodes
+                    .windows(2)
+                    .all(|nodes| nodes[0].start <= nodes[1].start),
+                "items are not sorted by interval start"
+            );
 
             if !nodes.is_empty() {
                 update_max(nodes);
@@ -43,24 +185,182 @@ where
     /// Construct a new tree by sorting the given `items`, in parallel
     ///
     /// Requires the `rayon` feature and dispatches tasks into the current [thread pool][rayon::ThreadPool].
+    ///
+    /// Orders items like [`new`][Self::new].
     pub fn par_new<I>(items: I) -> Self
     where
         I: IntoIterator<Item = Item<K, V>>,
         K: Send,
         V: Send,
     {
-        let mut nodes = items
-            .into_iter()
-            .map(|(interval, value)| {
-                let end = interval.end.clone();
-                ((interval, value), end)
-            })
-            .collect::<S>();
+        Self::par_sort_nodes(items.into_iter().map(node).collect())
+    }
 
+    #[cfg(feature = "threads")]
+    /// Construct a new tree by sorting the given `items`, in parallel using up to `threads` scoped threads
+    ///
+    /// Requires the `threads` feature and uses only the standard library.
+    ///
+    /// Orders items like [`new`][Self::new].
+    pub fn par_new_std<I>(items: I, threads: usize) -> Self
+    where
+        I: IntoIterator<Item = Item<K, V>>,
+        K: Send,
+        V: Send,
+    {
+        let mut nodes = items.into_iter().map(node).collect::<S>();
+
+        {
+            let nodes = nodes.as_mut();
+
+            scoped_sort(nodes, threads);
+
+            if !nodes.is_empty() {
+                scoped_update_max(nodes, threads);
+            }
+        }
+
+        Self {
+            nodes,
+            _marker: PhantomData,
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    /// Construct a new tree by sorting the given `items`, in parallel using the given thread `pool`
+    ///
+    /// Requires the `rayon` feature. The items are collected on the calling thread before the tasks are dispatched into `pool`.
+    ///
+    /// Orders items like [`new`][Self::new].
+    pub fn par_new_in<I>(items: I, pool: &ThreadPool) -> Self
+    where
+        I: IntoIterator<Item = Item<K, V>>,
+        K: Send,
+        V: Send,
+        S: Send,
+    {
+        let nodes = items.into_iter().map(node).collect();
+
+        pool.install(|| Self::par_sort_nodes(nodes))
+    }
+}
+
+impl<K, V, S> ITree<K, V, S>
+where
+    K: Ord + Clone,
+    S: AsMut<[Node<K, V>]>,
+{
+    /// Construct a new tree by sorting the given `items`, allocating storage for `capacity` items up front
+    ///
+    /// Useful if the number of items is known in advance but not reported by the [size hint][Iterator::size_hint] of `items`, e.g. because they are filtered, to avoid the over-allocation caused by repeatedly growing the storage. Iterators which report their exact length are already collected using a single allocation by [`new`][Self::new].
+    pub fn new_with_capacity<I>(items: I, capacity: usize) -> Self
+    where
+        I: IntoIterator<Item = Item<K, V>>,
+        S: From<Vec<Node<K, V>>>,
+    {
+        Self::fill_nodes(Vec::with_capacity(capacity), items)
+    }
+
+    fn fill_nodes<I>(mut nodes: Vec<Node<K, V>>, items: I) -> Self
+    where
+        I: IntoIterator<Item = Item<K, V>>,
+        S: From<Vec<Node<K, V>>>,
+    {
+        nodes.extend(items.into_iter().map(node));
+
+        Self::sort_nodes(nodes.into())
+    }
+
+    /// Construct a new tree from parallel arrays of interval starts, interval ends and values
+    ///
+    /// The items are sorted by sorting their indices which touches only the keys. Orders items like [`new`][Self::new].
+    ///
+    /// # Panics
+    ///
+    /// If the arrays do not have the same length.
+    pub fn from_columns(starts: &[K], ends: &[K], values: Vec<V>) -> Self
+    where
+        S: From<Vec<Node<K, V>>>,
+    {
+        assert_eq!(
+            starts.len(),
+            ends.len(),
+            "columns must have the same length"
+        );
+        assert_eq!(
+            starts.len(),
+            values.len(),
+            "columns must have the same length"
+        );
+
+        let mut perm = (0..starts.len()).collect::<Vec<_>>();
+
+        perm.sort_unstable_by(|&lhs, &rhs| {
+            starts[lhs]
+                .cmp(&starts[rhs])
+                .then_with(|| ends[rhs].cmp(&ends[lhs]))
+        });
+
+        let mut nodes = starts
+            .iter()
+            .zip(ends)
+            .zip(values)
+            .map(|((start, end), value)| node((start.clone()..end.clone(), value)))
+            .collect::<Vec<_>>();
+
+        for index in 0..perm.len() {
+            let mut curr = index;
+
+            loop {
+                let next = perm[curr];
+                perm[curr] = curr;
+
+                if next == index {
+                    break;
+                }
+
+                nodes.swap(curr, next);
+                curr = next;
+            }
+        }
+
+        if !nodes.is_empty() {
+            update_max(&mut nodes);
+        }
+
+        Self {
+            nodes: nodes.into(),
+            _marker: PhantomData,
+        }
+    }
+
+    fn sort_nodes(mut nodes: S) -> Self {
         {
             let nodes = nodes.as_mut();
 
-            nodes.par_sort_unstable_by(|lhs, rhs| (lhs.0).0.start.cmp(&(rhs.0).0.start));
+            sort(nodes);
+
+            if !nodes.is_empty() {
+                update_max(nodes);
+            }
+        }
+
+        Self {
+            nodes,
+            _marker: PhantomData,
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    fn par_sort_nodes(mut nodes: S) -> Self
+    where
+        K: Send,
+        V: Send,
+    {
+        {
+            let nodes = nodes.as_mut();
+
+            par_sort(nodes);
 
             if !nodes.is_empty() {
                 par_update_max(nodes);
@@ -87,7 +387,130 @@ where
     }
 }
 
-fn update_max<K, V>(nodes: &mut [Node<K, V>]) -> K
+#[cfg(feature = "rayon")]
+impl<K, V, S> FromParallelIterator<Item<K, V>> for ITree<K, V, S>
+where
+    K: Ord + Clone + Send,
+    V: Send,
+    S: AsMut<[Node<K, V>]> + FromParallelIterator<Node<K, V>>,
+{
+    fn from_par_iter<I>(items: I) -> Self
+    where
+        I: IntoParallelIterator<Item = Item<K, V>>,
+    {
+        Self::par_sort_nodes(items.into_par_iter().map(node).collect())
+    }
+}
+
+/// Error indicating that the item at the given index of the input has an empty or inverted interval
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EmptyInterval(pub usize);
+
+impl fmt::Display for EmptyInterval {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "item {} has an empty or inverted interval", self.0)
+    }
+}
+
+impl Error for EmptyInterval {}
+
+pub(crate) fn node<K, V>((interval, value): Item<K, V>) -> Node<K, V>
+where
+    K: Clone,
+{
+    Node {
+        max: interval.end.clone(),
+        start: interval.start,
+        end: interval.end,
+        value,
+    }
+}
+
+pub(crate) fn cmp<K, V>(lhs: &Node<K, V>, rhs: &Node<K, V>) -> Ordering
+where
+    K: Ord,
+{
+    cmp_intervals(&lhs.interval(), &rhs.interval())
+}
+
+pub(crate) fn cmp_intervals<K>(lhs: &Range<K>, rhs: &Range<K>) -> Ordering
+where
+    K: Ord,
+{
+    lhs.start
+        .cmp(&rhs.start)
+        .then_with(|| rhs.end.cmp(&lhs.end))
+}
+
+pub(crate) fn sort<K, V>(nodes: &mut [Node<K, V>])
+where
+    K: Ord,
+{
+    if !presorted(nodes) {
+        nodes.sort_unstable_by(cmp);
+    }
+}
+
+#[cfg(feature = "rayon")]
+pub(crate) fn par_sort<K, V>(nodes: &mut [Node<K, V>])
+where
+    K: Ord + Send,
+    V: Send,
+{
+    if !presorted(nodes) {
+        nodes.par_sort_unstable_by(cmp);
+    }
+}
+
+fn presorted<K, V>(nodes: &mut [Node<K, V>]) -> bool
+where
+    K: Ord,
+{
+    if nodes
+        .windows(2)
+        .all(|nodes| cmp(&nodes[0], &nodes[1]).is_le())
+    {
+        return true;
+    }
+
+    if nodes
+        .windows(2)
+        .all(|nodes| cmp(&nodes[0], &nodes[1]).is_ge())
+    {
+        nodes.reverse();
+        return true;
+    }
+
+    false
+}
+
+#[cfg(feature = "threads")]
+fn scoped_sort<K, V>(nodes: &mut [Node<K, V>], threads: usize)
+where
+    K: Ord + Send,
+    V: Send,
+{
+    if presorted(nodes) {
+        return;
+    }
+
+    if threads > 1 && nodes.len() >= 2 * threads {
+        let chunk_len = nodes.len().div_ceil(threads);
+
+        scope(|scope| {
+            for chunk in nodes.chunks_mut(chunk_len) {
+                scope.spawn(|| chunk.sort_unstable_by(cmp));
+            }
+        });
+
+        // The stable sort detects the sorted chunks and merges them.
+        nodes.sort_by(cmp);
+    } else {
+        nodes.sort_unstable_by(cmp);
+    }
+}
+
+pub(crate) fn update_max<K, V>(nodes: &mut [Node<K, V>])
 where
     K: Ord + Clone,
 {
@@ -96,18 +519,18 @@ where
     };
 
     if !left.is_empty() {
-        mid.1 = mid.1.clone().max(update_max(left));
+        update_max(left);
+        merge_max(mid, left);
     }
 
     if !right.is_empty() {
-        mid.1 = mid.1.clone().max(update_max(right));
+        update_max(right);
+        merge_max(mid, right);
     }
-
-    mid.1.clone()
 }
 
 #[cfg(feature = "rayon")]
-fn par_update_max<K, V>(nodes: &mut [Node<K, V>]) -> K
+pub(crate) fn par_update_max<K, V>(nodes: &mut [Node<K, V>])
 where
     K: Ord + Clone + Send,
     V: Send,
@@ -119,17 +542,342 @@ where
     match (left.is_empty(), right.is_empty()) {
         (true, true) => (),
         (false, true) => {
-            mid.1 = mid.1.clone().max(update_max(left));
+            update_max(left);
+            merge_max(mid, left);
         }
         (true, false) => {
-            mid.1 = mid.1.clone().max(update_max(right));
+            update_max(right);
+            merge_max(mid, right);
         }
         (false, false) => {
-            let (left, right) = join(|| update_max(left), || update_max(right));
+            join(|| update_max(left), || update_max(right));
+
+            merge_max(mid, left);
+            merge_max(mid, right);
+        }
+    }
+}
+
+#[cfg(feature = "threads")]
+fn scoped_update_max<K, V>(nodes: &mut [Node<K, V>], threads: usize)
+where
+    K: Ord + Clone + Send,
+    V: Send,
+{
+    if threads <= 1 {
+        update_max(nodes);
+        return;
+    }
+
+    let (left, [mid, right @ ..]) = nodes.split_at_mut(nodes.len() / 2) else {
+        unreachable!()
+    };
+
+    scope(|scope| {
+        if !left.is_empty() {
+            scope.spawn(|| scoped_update_max(left, threads / 2));
+        }
+
+        if !right.is_empty() {
+            scoped_update_max(right, threads - threads / 2);
+        }
+    });
+
+    if !left.is_empty() {
+        merge_max(mid, left);
+    }
+
+    if !right.is_empty() {
+        merge_max(mid, right);
+    }
+}
+
+pub(crate) fn merge_max<K, V>(mid: &mut Node<K, V>, nodes: &[Node<K, V>])
+where
+    K: Ord + Clone,
+{
+    let max = &nodes[nodes.len() / 2].max;
+
+    if mid.max < *max {
+        mid.max = max.clone();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::ops::ControlFlow;
+
+    use proptest::{collection::vec, test_runner::TestRunner};
+
+    #[test]
+    fn new_presorted() {
+        let items = (0..100)
+            .map(|start| (start..start + 10, ()))
+            .collect::<Vec<_>>();
+
+        let tree = ITree::<_, _>::new(items.iter().cloned());
+        assert!(tree.iter().eq(&items));
+
+        let tree = ITree::<_, _>::new(items.iter().rev().cloned());
+        assert!(tree.iter().eq(&items));
+        assert!(tree.iter_rev().eq(items.iter().rev()));
+        assert!(tree.iter().rev().eq(tree.iter_rev()));
+    }
+
+    #[test]
+    fn get_yields_items() {
+        let tree = ITree::<_, _>::new([(1..2, 'b'), (0..5, 'a'), (2..3, 'c')]);
 
-            mid.1 = mid.1.clone().max(left.max(right));
+        let entry = tree.get(1).unwrap();
+        assert_eq!(entry.interval(), &1..&2);
+        assert_eq!(*entry.value(), 'b');
+
+        assert!(tree.get(3).is_none());
+    }
+
+    #[test]
+    fn max_ends_follow_subtrees() {
+        let tree = ITree::<_, _>::new([(0..5, ()), (1..2, ()), (2..3, ()), (3..4, ())]);
+
+        assert_eq!(tree.nodes().len(), 4);
+        assert!(tree.max_ends().eq(&[5, 5, 5, 4]));
+    }
+
+    #[test]
+    fn new_is_deterministic() {
+        let items = [(0..3, ()), (0..1, ()), (1..2, ()), (0..2, ())];
+
+        let tree1 = ITree::<_, _>::new(items.iter().cloned());
+        let tree2 = ITree::<_, _>::new(items.iter().rev().cloned());
+        assert_eq!(&*tree1, &*tree2);
+
+        assert!(tree1
+            .iter()
+            .eq(&[(0..3, ()), (0..2, ()), (0..1, ()), (1..2, ())]));
+    }
+
+    #[test]
+    fn trees_compare_items() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        fn hash<T: Hash>(value: &T) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
         }
+
+        let tree1 = ITree::<_, _>::new([(0..3, 'a'), (1..2, 'b')]);
+        let tree2 = ITree::<_, _, Vec<_>>::new([(1..2, 'b'), (0..3, 'a')]);
+        assert!(tree1 == tree2);
+        assert_eq!(hash(&tree1), hash(&tree2.into_boxed()));
+
+        let tree3 = ITree::<_, _>::new([(0..3, 'a'), (1..2, 'c')]);
+        assert_ne!(tree1, tree3);
+        assert_ne!(hash(&tree1), hash(&tree3));
+    }
+
+    #[test]
+    fn new_by_cached_key_random() {
+        const DOM: Range<i32> = -1000..1000;
+        const LEN: usize = 1000_usize;
+
+        TestRunner::default()
+            .run(&(vec(DOM, LEN), vec(DOM, LEN)), |(start, end)| {
+                let items = start
+                    .iter()
+                    .zip(&end)
+                    .map(|(&start, &end)| (start.to_string()..end.to_string(), ()))
+                    .collect::<Vec<_>>();
+
+                let tree1 = ITree::<_, _>::new(items.iter().cloned());
+                let tree2 = ITree::<_, _>::new_by_cached_key(items, |key| key.as_bytes().to_vec());
+                assert_eq!(&*tree1, &*tree2);
+
+                Ok(())
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn new_dedup_combines_values() {
+        let tree = ITree::<_, _>::new_dedup(
+            [(0..2, 1), (1..2, 2), (0..2, 3), (1..3, 4), (0..2, 5)],
+            |lhs, rhs| lhs * 10 + rhs,
+        );
+
+        assert!(tree.is_valid());
+        assert!(tree.iter().eq(&[(0..2, 135), (1..3, 4), (1..2, 2)]));
+    }
+
+    #[test]
+    fn new_strict_rejects_empty_intervals() {
+        let items = [
+            (0..1, ()),
+            (2..2, ()),
+            (3..4, ()),
+            (Range { start: 6, end: 5 }, ()),
+        ];
+
+        assert_eq!(
+            ITree::<_, _>::new_strict(items.iter().cloned()).unwrap_err(),
+            EmptyInterval(1)
+        );
+
+        let tree = ITree::<_, _>::new_non_empty(items);
+        assert!(tree.iter().eq(&[(0..1, ()), (3..4, ())]));
+    }
+
+    #[test]
+    fn new_with_capacity_allocates_once() {
+        let items = (0..1000).filter(|start| start % 3 != 0);
+
+        let tree = ITree::<_, _, Vec<_>>::new_with_capacity(
+            items.map(|start| (start..start + 1, ())),
+            666,
+        );
+        assert!(tree.is_valid());
+        assert_eq!(tree.len(), 666);
+        assert!(tree.nodes.capacity() >= 666);
+
+        let nodes = Vec::with_capacity(666);
+        let (ptr, capacity) = (nodes.as_ptr(), nodes.capacity());
+
+        let tree = ITree::<_, _, Vec<_>>::fill_nodes(
+            nodes,
+            (0..1000)
+                .filter(|start| start % 3 != 0)
+                .map(|start| (start..start + 1, ())),
+        );
+        assert!(tree.is_valid());
+        assert_eq!(tree.nodes.as_ptr(), ptr);
+        assert_eq!(tree.nodes.capacity(), capacity);
+    }
+
+    #[test]
+    fn from_columns_random() {
+        const DOM: Range<i32> = -1000..1000;
+        const LEN: usize = 1000_usize;
+
+        TestRunner::default()
+            .run(&(vec(DOM, LEN), vec(DOM, LEN)), |(start, end)| {
+                let values = (0..LEN).collect::<Vec<_>>();
+
+                let tree1 = ITree::<_, _>::from_columns(&start, &end, values);
+                assert!(tree1.is_valid());
+
+                let tree2 = ITree::<_, _>::new(
+                    start
+                        .iter()
+                        .zip(&end)
+                        .enumerate()
+                        .map(|(index, (&start, &end))| (start..end, index)),
+                );
+                assert!(tree1
+                    .iter()
+                    .map(Node::interval)
+                    .eq(tree2.iter().map(Node::interval)));
+
+                for node in tree1.iter() {
+                    assert_eq!(node.start..node.end, start[node.value]..end[node.value]);
+                }
+
+                let (starts, ends, values) = tree1.into_columns();
+                assert_eq!(values.len(), LEN);
+
+                let tree3 = ITree::<_, _>::from_columns(&starts, &ends, values);
+                assert!(tree3
+                    .iter()
+                    .map(Node::interval)
+                    .eq(tree2.iter().map(Node::interval)));
+
+                Ok(())
+            })
+            .unwrap()
     }
 
-    mid.1.clone()
+    #[test]
+    fn try_new_stops_at_error() {
+        let tree = ITree::<_, _>::try_new([Ok::<_, ()>((0..1, ())), Ok((1..2, ()))]).unwrap();
+        assert_eq!(tree.len(), 2);
+
+        let err =
+            ITree::<_, _>::try_new([Ok((0..1, ())), Err("invalid"), Ok((1..2, ()))]).unwrap_err();
+        assert_eq!(err, "invalid");
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn collect_in_parallel() {
+        let tree = (0..100)
+            .into_par_iter()
+            .map(|start| (start..start + 10, start))
+            .collect::<ITree<_, _>>();
+
+        assert!(tree.iter().eq(&(0..100)
+            .map(|start| (start..start + 10, start))
+            .collect::<Vec<_>>()));
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn par_new_in_pool() {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(2)
+            .build()
+            .unwrap();
+
+        let items = (0..1000)
+            .map(|start| (start..start + 10, start))
+            .collect::<Vec<_>>();
+
+        let tree = ITree::<_, _>::par_new_in(items.iter().rev().cloned(), &pool);
+        assert!(tree.is_valid());
+        assert!(tree.iter().eq(&items));
+    }
+
+    #[test]
+    fn from_sorted_random() {
+        const DOM: Range<i32> = -1000..1000;
+        const LEN: usize = 1000_usize;
+
+        TestRunner::default()
+            .run(
+                &(vec(DOM, LEN), vec(DOM, LEN), DOM, DOM),
+                |(start, end, query_start, query_end)| {
+                    let mut items = start
+                        .iter()
+                        .zip(&end)
+                        .map(|(&start, &end)| (start..end, ()))
+                        .collect::<Vec<_>>();
+
+                    items.sort_by_key(|(range, ())| range.start);
+
+                    let tree = ITree::<_, _>::from_sorted(items.iter().cloned());
+
+                    assert!(tree.iter().eq(&items));
+
+                    let mut result1 = Vec::new();
+                    let _ = tree.query(query_start..query_end, |entry| {
+                        result1.push(entry.interval());
+                        ControlFlow::Continue(())
+                    });
+
+                    let mut result2 = items
+                        .iter()
+                        .filter(|(range, ())| query_end > range.start && query_start < range.end)
+                        .map(|(range, ())| &range.start..&range.end)
+                        .collect::<Vec<_>>();
+
+                    result1.sort_unstable_by_key(|range| (range.start, range.end));
+                    result2.sort_unstable_by_key(|range| (range.start, range.end));
+                    assert_eq!(result1, result2);
+
+                    Ok(())
+                },
+            )
+            .unwrap()
+    }
 }
@@ -0,0 +1,226 @@
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use crate::{ITree, Item, Node};
+
+impl<K, V, S> ITree<K, V, S> {
+    /// The underlying storage of the nodes
+    pub fn as_inner(&self) -> &S {
+        &self.nodes
+    }
+
+    /// Recover the underlying storage of the nodes
+    pub fn into_inner(self) -> S {
+        self.nodes
+    }
+
+    /// Move the nodes into a vector, e.g. to [extend][Extend] the tree
+    pub fn into_vec(self) -> ITree<K, V, Vec<Node<K, V>>>
+    where
+        S: Into<Vec<Node<K, V>>>,
+    {
+        ITree {
+            nodes: self.nodes.into(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Move the nodes into a boxed slice, dropping any excess capacity
+    pub fn into_boxed(self) -> ITree<K, V, Box<[Node<K, V>]>>
+    where
+        S: Into<Box<[Node<K, V>]>>,
+    {
+        ITree {
+            nodes: self.nodes.into(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Move the items into parallel arrays of interval starts, interval ends and values, ordered by interval start
+    ///
+    /// The inverse of [`from_columns`][Self::from_columns], e.g. for writing the items using a columnar format like Parquet.
+    pub fn into_columns(self) -> (Vec<K>, Vec<K>, Vec<V>)
+    where
+        S: Into<Vec<Node<K, V>>>,
+    {
+        let nodes: Vec<_> = self.nodes.into();
+
+        let mut starts = Vec::with_capacity(nodes.len());
+        let mut ends = Vec::with_capacity(nodes.len());
+        let mut values = Vec::with_capacity(nodes.len());
+
+        for node in nodes {
+            starts.push(node.start);
+            ends.push(node.end);
+            values.push(node.value);
+        }
+
+        (starts, ends, values)
+    }
+
+    /// Move the nodes into reference-counted storage so that clones of the tree are cheap and can be sent to other threads
+    pub fn into_shared(self) -> ITree<K, V, Arc<[Node<K, V>]>>
+    where
+        S: Into<Arc<[Node<K, V>]>>,
+    {
+        ITree {
+            nodes: self.nodes.into(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<K, V> ITree<K, V, Arc<[Node<K, V>]>>
+where
+    K: Ord + Clone,
+{
+    /// Construct a new tree using reference-counted storage by sorting the given `items`
+    pub fn new_shared<I>(items: I) -> Self
+    where
+        I: IntoIterator<Item = Item<K, V>>,
+    {
+        ITree::<K, V, Vec<Node<K, V>>>::new(items).into_shared()
+    }
+}
+
+impl<K, V, S> ITree<K, V, S>
+where
+    S: AsRef<[Node<K, V>]>,
+{
+    /// Clone the nodes into a vector
+    pub fn to_vec_backed(&self) -> ITree<K, V, Vec<Node<K, V>>>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        ITree {
+            nodes: self.nodes.as_ref().to_vec(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Borrow the nodes as a lightweight tree which can be passed by value
+    pub fn as_view(&self) -> ITree<K, V, &[Node<K, V>]> {
+        ITree {
+            nodes: self.nodes.as_ref(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Borrow the nodes as copy-on-write storage which is cloned only if a rebuild needs owned nodes
+    pub fn as_cow(&self) -> ITree<K, V, Cow<'_, [Node<K, V>]>>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        ITree {
+            nodes: Cow::Borrowed(self.nodes.as_ref()),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<K, V> ITree<K, V, Cow<'_, [Node<K, V>]>>
+where
+    K: Clone,
+    V: Clone,
+{
+    /// Whether the nodes are borrowed, i.e. have not been cloned yet
+    pub fn is_borrowed(&self) -> bool {
+        matches!(self.nodes, Cow::Borrowed(_))
+    }
+
+    /// Convert into owned storage, cloning the nodes if they are still borrowed
+    pub fn into_owned(self) -> ITree<K, V, Vec<Node<K, V>>> {
+        ITree {
+            nodes: self.nodes.into_owned(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::ops::ControlFlow;
+    use std::thread::spawn;
+
+    #[test]
+    fn shared_trees_are_cloned_cheaply() {
+        let tree = ITree::<_, _>::new([(0..1, 0), (1..2, 1)]).into_shared();
+
+        let clone = tree.clone();
+        assert!(Arc::ptr_eq(&tree.nodes, &clone.nodes));
+
+        let len = spawn(move || clone.len()).join().unwrap();
+        assert_eq!(len, 2);
+
+        let tree = ITree::new_shared([(0..1, 0)]);
+        assert!(tree.iter().eq(&[(0..1, 0)]));
+    }
+
+    #[test]
+    fn recover_storage() {
+        let tree = ITree::<_, _, Vec<_>>::new([(1..2, 1), (0..1, 0)]);
+        assert_eq!(tree.as_inner().len(), 2);
+
+        let nodes = tree.into_inner();
+        assert_eq!(nodes[0].start, 0);
+        assert!(ITree::<_, _, _>::from_nodes(nodes).is_ok());
+    }
+
+    #[test]
+    fn convert_storage() {
+        let tree = ITree::<_, _>::new([(0..1, 0), (1..2, 1)]);
+
+        let mut tree = tree.as_view().to_vec_backed();
+        tree.extend([(2..3, 2)]);
+
+        let tree = tree.into_boxed().into_shared();
+        assert!(tree.check().is_ok());
+
+        let tree = tree.as_cow().into_owned().into_vec();
+        assert!(tree.iter().eq(&[(0..1, 0), (1..2, 1), (2..3, 2)]));
+    }
+
+    #[test]
+    fn views_borrow_the_nodes() {
+        let tree = ITree::<_, _>::new([(0..1, 0), (1..2, 1)]);
+
+        fn count<S>(tree: ITree<i32, i32, S>) -> usize
+        where
+            S: AsRef<[Node<i32, i32>]>,
+        {
+            let mut count = 0;
+            let _ = tree.query(0..2, |_entry| {
+                count += 1;
+                ControlFlow::Continue(())
+            });
+            count
+        }
+
+        let view = tree.as_view();
+        assert!(std::ptr::eq(view.nodes, &*tree.nodes));
+        assert_eq!(count(view), 2);
+        assert_eq!(count(tree.as_view()), 2);
+    }
+
+    #[test]
+    fn cow_trees_are_cloned_on_rebuild() {
+        let tree = ITree::<_, _>::new([(0..1, 0), (1..2, 1), (2..3, 2)]);
+
+        let cow = tree.as_cow();
+        assert!(cow.is_borrowed());
+        assert!(cow.check().is_ok());
+
+        let cow = cow.retain(|(_interval, value)| *value != 1);
+        assert!(!cow.is_borrowed());
+        assert!(cow.into_owned().iter().eq(&[(0..1, 0), (2..3, 2)]));
+
+        assert_eq!(tree.len(), 3);
+    }
+}
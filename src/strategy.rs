@@ -0,0 +1,84 @@
+//! [`proptest`] strategies generating trees together with their items
+//!
+//! Requires the `proptest` feature. The items can serve as a brute-force reference when testing code which queries the trees.
+
+use alloc::vec::Vec;
+use core::fmt::Debug;
+use core::ops::Add;
+
+use proptest::collection::{vec, SizeRange};
+use proptest::strategy::Strategy;
+
+use crate::{ITree, Item};
+
+/// Generate items whose intervals start at values drawn from `starts`, have lengths drawn from `lens` and carry values drawn from `values`
+///
+/// The number of items is drawn from `size`. Adding a start and a length must not overflow `K`.
+pub fn items<K, V>(
+    starts: impl Strategy<Value = K>,
+    lens: impl Strategy<Value = K>,
+    values: impl Strategy<Value = V>,
+    size: impl Into<SizeRange>,
+) -> impl Strategy<Value = Vec<Item<K, V>>>
+where
+    K: Clone + Debug + Add<Output = K>,
+    V: Debug,
+{
+    vec((starts, lens, values), size).prop_map(|items| {
+        items
+            .into_iter()
+            .map(|(start, len, value)| (start.clone()..start + len, value))
+            .collect()
+    })
+}
+
+/// Generate trees built from [`items`] together with the items in the order they were generated
+pub fn itree<K, V>(
+    starts: impl Strategy<Value = K>,
+    lens: impl Strategy<Value = K>,
+    values: impl Strategy<Value = V>,
+    size: impl Into<SizeRange>,
+) -> impl Strategy<Value = (ITree<K, V>, Vec<Item<K, V>>)>
+where
+    K: Ord + Clone + Debug + Add<Output = K>,
+    V: Clone + Debug,
+{
+    items(starts, lens, values, size).prop_map(|items| (ITree::new(items.clone()), items))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::ops::ControlFlow;
+
+    use proptest::test_runner::TestRunner;
+
+    #[test]
+    fn trees_match_items() {
+        TestRunner::default()
+            .run(
+                &(itree(0_u32..100, 0_u32..10, 0_u8..5, 0..50), 0_u32..110),
+                |((tree, items), point)| {
+                    assert!(tree.is_valid());
+                    assert_eq!(tree.len(), items.len());
+
+                    let mut count = 0;
+                    let _ = tree.query(point..point + 1, |_| {
+                        count += 1;
+                        ControlFlow::Continue(())
+                    });
+
+                    let expected = items
+                        .iter()
+                        .filter(|(interval, _)| interval.contains(&point))
+                        .count();
+
+                    assert_eq!(count, expected);
+
+                    Ok(())
+                },
+            )
+            .unwrap();
+    }
+}
@@ -0,0 +1,282 @@
+use core::error::Error;
+use core::fmt;
+use core::marker::PhantomData;
+
+use crate::{ITree, Node};
+
+impl<K, V, S> ITree<K, V, S>
+where
+    K: Ord,
+    S: AsRef<[Node<K, V>]>,
+{
+    /// Interprets the given `nodes` as a tree after verifying that they are organized as an interval tree
+    ///
+    /// This is the checked counterpart to [`new_unchecked`][Self::new_unchecked] and takes time linear in the number of nodes.
+    pub fn from_nodes(nodes: S) -> Result<Self, InvalidTree> {
+        validate(nodes.as_ref())?;
+
+        Ok(Self {
+            nodes,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Interprets the given `nodes` as a tree, verifying them only if debug assertions are enabled
+    ///
+    /// Catches invalid `nodes`, e.g. produced by a faulty writer, during development while costing nothing compared to [`new_unchecked`][Self::new_unchecked] in release builds.
+    ///
+    /// # Panics
+    ///
+    /// If debug assertions are enabled and the `nodes` are not organized as an interval tree, with the index of the first offending node.
+    pub fn new_debug_checked(nodes: S) -> Self {
+        if cfg!(debug_assertions) {
+            if let Err(err) = validate(nodes.as_ref()) {
+                panic!("invalid tree: {err}");
+            }
+        }
+
+        Self {
+            nodes,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Verify that the nodes are organized as an interval tree, reporting the first violation
+    ///
+    /// Useful to sanity-check trees constructed via [`new_unchecked`][Self::new_unchecked].
+    pub fn check(&self) -> Result<(), InvalidTree> {
+        validate(self.nodes.as_ref())
+    }
+
+    /// Whether the nodes are organized as an interval tree
+    pub fn is_valid(&self) -> bool {
+        self.check().is_ok()
+    }
+}
+
+macro_rules! impl_is_valid_const {
+    ($($ty:ty),*) => {
+        $(
+            impl<'a, V> ITree<$ty, V, &'a [Node<$ty, V>]> {
+                /// Whether the nodes are organized as an interval tree, usable in const context
+                pub const fn is_valid_const(&self) -> bool {
+                    let nodes = self.nodes;
+
+                    let mut index = 1;
+
+                    while index < nodes.len() {
+                        if nodes[index - 1].start > nodes[index].start {
+                            return false;
+                        }
+
+                        index += 1;
+                    }
+
+                    nodes.is_empty() || Self::valid_max(nodes).is_some()
+                }
+
+                const fn valid_max(nodes: &[Node<$ty, V>]) -> Option<$ty> {
+                    let (left, rest) = nodes.split_at(nodes.len() / 2);
+                    let (mid, right) = rest.split_at(1);
+                    let mid = &mid[0];
+
+                    let mut max = mid.end;
+
+                    if !left.is_empty() {
+                        match Self::valid_max(left) {
+                            Some(left) if left > max => max = left,
+                            Some(_) => (),
+                            None => return None,
+                        }
+                    }
+
+                    if !right.is_empty() {
+                        match Self::valid_max(right) {
+                            Some(right) if right > max => max = right,
+                            Some(_) => (),
+                            None => return None,
+                        }
+                    }
+
+                    if mid.max != max {
+                        return None;
+                    }
+
+                    Some(max)
+                }
+            }
+        )*
+    };
+}
+
+impl_is_valid_const!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// Error indicating that nodes are not organized as an interval tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidTree {
+    /// The node at the given index starts before its predecessor
+    Unsorted(usize),
+    /// The node at the given index does not store the maximum of the interval upper bounds in its subtree
+    InvalidMax(usize),
+    /// The node at the given index refers to a value which does not exist
+    DanglingValue(usize),
+}
+
+impl fmt::Display for InvalidTree {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unsorted(index) => write!(fmt, "node {index} starts before its predecessor"),
+            Self::InvalidMax(index) => write!(fmt, "node {index} has an invalid maximum end"),
+            Self::DanglingValue(index) => write!(fmt, "node {index} refers to a missing value"),
+        }
+    }
+}
+
+impl Error for InvalidTree {}
+
+pub(crate) fn validate<K, V>(nodes: &[Node<K, V>]) -> Result<(), InvalidTree>
+where
+    K: Ord,
+{
+    validate_with(nodes.len(), |index| {
+        let node = &nodes[index];
+
+        (&node.start, &node.end, &node.max)
+    })
+}
+
+/// Validates nodes of any representation given access to the start, end and maximum end of the node at each index
+pub(crate) fn validate_with<'a, K, F>(len: usize, bounds: F) -> Result<(), InvalidTree>
+where
+    K: Ord + 'a,
+    F: Fn(usize) -> (&'a K, &'a K, &'a K),
+{
+    if let Some(index) = (1..len).find(|&index| bounds(index - 1).0 > bounds(index).0) {
+        return Err(InvalidTree::Unsorted(index));
+    }
+
+    if len != 0 {
+        validate_max(0, len, &bounds)?;
+    }
+
+    Ok(())
+}
+
+fn validate_max<'a, K, F>(offset: usize, len: usize, bounds: &F) -> Result<&'a K, InvalidTree>
+where
+    K: Ord + 'a,
+    F: Fn(usize) -> (&'a K, &'a K, &'a K),
+{
+    let left_len = len / 2;
+    let right_len = len - left_len - 1;
+
+    let index = offset + left_len;
+
+    let (_start, end, mid_max) = bounds(index);
+
+    let mut max = end;
+
+    if left_len != 0 {
+        max = max.max(validate_max(offset, left_len, bounds)?);
+    }
+
+    if right_len != 0 {
+        max = max.max(validate_max(index + 1, right_len, bounds)?);
+    }
+
+    if mid_max != max {
+        return Err(InvalidTree::InvalidMax(index));
+    }
+
+    Ok(mid_max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_nodes_rejects_invalid_trees() {
+        let tree = ITree::<_, _>::new([(0..5, ()), (1..2, ()), (2..3, ())]);
+
+        let nodes = tree.to_vec();
+        assert!(ITree::<_, _, _>::from_nodes(nodes).is_ok());
+
+        let mut nodes = tree.to_vec();
+        nodes.swap(0, 2);
+        assert_eq!(
+            ITree::<_, _, _>::from_nodes(nodes).unwrap_err(),
+            InvalidTree::Unsorted(1)
+        );
+
+        let mut nodes = tree.to_vec();
+        nodes[1].max = 3;
+        assert_eq!(
+            ITree::<_, _, _>::from_nodes(nodes).unwrap_err(),
+            InvalidTree::InvalidMax(1)
+        );
+    }
+
+    #[test]
+    fn check_trees() {
+        let tree = ITree::<_, _>::new([(0..5, ()), (1..2, ()), (2..3, ())]);
+        assert!(tree.is_valid());
+
+        let mut nodes = tree.to_vec();
+        nodes[2].max = 2;
+        let tree = ITree::<_, _, _> {
+            nodes,
+            _marker: PhantomData,
+        };
+        assert!(!tree.is_valid());
+        assert_eq!(tree.check().unwrap_err(), InvalidTree::InvalidMax(2));
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "invalid tree: node 1 starts before its predecessor")]
+    fn new_debug_checked_panics_on_invalid_trees() {
+        let tree = ITree::<_, _>::new([(0..1, ()), (1..2, ())]);
+
+        let mut nodes = tree.to_vec();
+        nodes.swap(0, 1);
+        ITree::<_, _, _>::new_debug_checked(nodes);
+    }
+
+    #[test]
+    fn static_trees() {
+        static TREE: ITree<u32, char, &[Node<u32, char>]> = ITree::new_unchecked(&[
+            Node {
+                start: 0,
+                end: 5,
+                max: 5,
+                value: 'a',
+            },
+            Node {
+                start: 1,
+                end: 2,
+                max: 5,
+                value: 'b',
+            },
+            Node {
+                start: 2,
+                end: 3,
+                max: 3,
+                value: 'c',
+            },
+        ]);
+
+        const _: () = assert!(TREE.is_valid_const());
+        assert!(TREE.is_valid());
+
+        let tree = ITree::<_, _>::new([(0..5, 'a'), (1..2, 'b'), (2..3, 'c')]);
+        assert_eq!(TREE.as_inner(), &&*tree.nodes);
+
+        let mut nodes = tree.to_vec();
+        nodes[2].max = 2;
+        assert!(!ITree::new_unchecked(&nodes[..]).is_valid_const());
+
+        nodes.swap(0, 2);
+        assert!(!ITree::new_unchecked(&nodes[..]).is_valid_const());
+    }
+}
@@ -0,0 +1,251 @@
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::marker::PhantomData;
+use std::mem::{offset_of, size_of};
+
+use crate::format::{encode_header, encode_node, Crc32c, HEADER_LEN};
+use crate::{Codec, Item, Node};
+
+/// Writes a tree incrementally without holding it in memory
+///
+/// Accepts exactly `len` items sorted by interval start and produces the same versioned binary format as [`write_to`][crate::ITree::write_to], so that the result can be loaded using [`read_from`][crate::ITree::read_from] or [`from_bytes`][crate::ITree::from_bytes]. Maxima which are only known after later items were seen are patched by seeking back, keeping only a stack of size logarithmic in `len`. As the checksum covers the patched maxima, [`finish`][Self::finish] reads the written nodes back once to compute it before appending it. Requires the `std` feature.
+#[derive(Debug)]
+pub struct ITreeWriter<K, V, W> {
+    writer: W,
+    offset: u64,
+    len: usize,
+    index: usize,
+    last_start: Option<K>,
+    stack: Vec<Frame<K>>,
+    buf: Vec<u8>,
+    _marker: PhantomData<V>,
+}
+
+#[derive(Debug)]
+struct Frame<K> {
+    hi: usize,
+    mid: usize,
+    max: Option<K>,
+    dirty: bool,
+}
+
+impl<K, V, W> ITreeWriter<K, V, W>
+where
+    K: Ord + Clone + Codec,
+    V: Codec,
+    W: Read + Write + Seek,
+{
+    /// Start writing a tree of `len` items at the current position of `writer`
+    ///
+    /// The header is written immediately whereas the checksum is appended by [`finish`][Self::finish].
+    ///
+    /// Fails like [`ITree::write_to`][crate::ITree::write_to] if `K` or `V` cannot be written using the binary format.
+    pub fn new(mut writer: W, len: usize) -> io::Result<Self> {
+        let offset = writer.stream_position()?;
+
+        writer.write_all(&encode_header::<K, V>(len)?)?;
+
+        Ok(Self {
+            writer,
+            offset,
+            len,
+            index: 0,
+            last_start: None,
+            stack: Vec::new(),
+            buf: vec![0; size_of::<Node<K, V>>()],
+            _marker: PhantomData,
+        })
+    }
+
+    /// Write the next item
+    ///
+    /// Fails with [`InvalidInput`][io::ErrorKind::InvalidInput] if the item starts before its predecessor or if all `len` items were already written.
+    pub fn push(&mut self, item: Item<K, V>) -> io::Result<()> {
+        if self.index == self.len {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "more items than announced",
+            ));
+        }
+
+        if let Some(last_start) = &self.last_start {
+            if *last_start > item.0.start {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "items are not sorted by interval start",
+                ));
+            }
+        }
+
+        let index = self.index;
+
+        let range = match self.stack.last() {
+            Some(frame) if frame.mid == index => None,
+            Some(frame) if frame.mid < index => Some((frame.mid + 1, frame.hi)),
+            Some(frame) => unreachable!("frame {} is not an ancestor", frame.mid),
+            None => Some((0, self.len)),
+        };
+
+        if let Some((lo, mut hi)) = range {
+            loop {
+                let mid = lo + (hi - lo) / 2;
+
+                self.stack.push(Frame {
+                    hi,
+                    mid,
+                    max: None,
+                    dirty: false,
+                });
+
+                if mid == index {
+                    break;
+                }
+
+                hi = mid;
+            }
+        }
+
+        for frame in &mut self.stack {
+            match &frame.max {
+                Some(max) if *max >= item.0.end => (),
+                _ => {
+                    frame.max = Some(item.0.end.clone());
+                    frame.dirty = frame.mid < index;
+                }
+            }
+        }
+
+        let max = self.stack.last().unwrap().max.clone().unwrap();
+
+        let (interval, value) = item;
+
+        let node = Node {
+            start: interval.start,
+            end: interval.end,
+            max,
+            value,
+        };
+
+        encode_node(&node, &mut self.buf);
+        self.writer.write_all(&self.buf)?;
+
+        self.index += 1;
+        self.last_start = Some(node.start);
+
+        while let Some(frame) = self.stack.last() {
+            if frame.hi != self.index {
+                break;
+            }
+
+            let frame = self.stack.pop().unwrap();
+
+            if frame.dirty {
+                self.patch(frame.mid, &frame.max.unwrap())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn pos(&self, index: usize) -> u64 {
+        self.offset + (HEADER_LEN + index * size_of::<Node<K, V>>()) as u64
+    }
+
+    fn patch(&mut self, index: usize, max: &K) -> io::Result<()> {
+        max.encode(&mut self.buf);
+
+        self.writer.seek(SeekFrom::Start(
+            self.pos(index) + offset_of!(Node<K, V>, max) as u64,
+        ))?;
+        self.writer.write_all(&self.buf[..K::SIZE])?;
+        self.writer.seek(SeekFrom::Start(self.pos(self.index)))?;
+
+        Ok(())
+    }
+
+    /// Finish writing the tree by appending the checksum and return the underlying writer positioned after it
+    ///
+    /// Fails with [`InvalidInput`][io::ErrorKind::InvalidInput] if fewer than `len` items were written.
+    pub fn finish(mut self) -> io::Result<W> {
+        if self.index != self.len {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "fewer items than announced",
+            ));
+        }
+
+        let mut crc = Crc32c::new();
+        crc.update(&encode_header::<K, V>(self.len)?);
+
+        self.writer.seek(SeekFrom::Start(self.pos(0)))?;
+
+        for _ in 0..self.len {
+            self.writer.read_exact(&mut self.buf)?;
+
+            crc.update(&self.buf);
+        }
+
+        self.writer.write_all(&crc.finish().to_le_bytes())?;
+        self.writer.flush()?;
+
+        Ok(self.writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::Cursor;
+
+    use proptest::{collection::vec, test_runner::TestRunner};
+
+    use crate::ITree;
+
+    #[test]
+    fn write_random() {
+        TestRunner::default()
+            .run(
+                &(vec(-1000_i32..1000, 0..200), vec(-1000_i32..1000, 200)),
+                |(start, end)| {
+                    let tree = ITree::<_, _>::new(
+                        start
+                            .iter()
+                            .zip(&end)
+                            .map(|(&start, &end)| (start..end, start as u16)),
+                    );
+
+                    let mut cursor = Cursor::new(vec![0xff; 3]);
+                    cursor.set_position(3);
+
+                    let mut writer = ITreeWriter::new(cursor, tree.len()).unwrap();
+
+                    for &node in tree.iter() {
+                        writer.push(node.into_item()).unwrap();
+                    }
+
+                    let cursor = writer.finish().unwrap();
+                    assert_eq!(cursor.position(), cursor.get_ref().len() as u64);
+
+                    let buf = cursor.into_inner();
+                    assert_eq!(buf[3..], tree.write_to(Vec::new()).unwrap());
+
+                    let read = ITree::<i32, u16>::read_from(&buf[3..]).unwrap();
+                    assert_eq!(read.as_inner(), tree.as_inner());
+
+                    Ok(())
+                },
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn reject_unsorted_items() {
+        let mut writer = ITreeWriter::new(Cursor::new(Vec::new()), 2).unwrap();
+
+        writer.push((1..2, ())).unwrap();
+        assert_eq!(
+            writer.push((0..1, ())).unwrap_err().kind(),
+            io::ErrorKind::InvalidInput
+        );
+    }
+}